@@ -5,13 +5,24 @@ use indicatif::{ProgressBar, ProgressStyle};
 
 /// Create a `ProgressBar` out of a `BudgetSummary`
 pub fn budget_pbar(summary: BudgetSummary) -> ProgressBar {
-    if let (Some(val), Some(frac)) = (summary.allotment_rounded(), summary.progress_frac()) {
+    if let Some(val) = summary.available_rounded() {
         let pbar = ProgressBar::new(val);
         let bar_colour: &str;
+        // with nothing available, any progress has used it all up
+        let frac = summary.progress_frac().unwrap_or(1.0);
 
-        if *frac > 1.0 {
+        if summary.is_income() {
+            // income budgets are targets to reach, so falling short is what gets flagged
+            if !summary.is_under_target() {
+                bar_colour = "green";
+            } else if frac >= 0.5 {
+                bar_colour = "yellow";
+            } else {
+                bar_colour = "red";
+            }
+        } else if summary.is_over_budget() {
             bar_colour = "red";
-        } else if *frac > 0.5 {
+        } else if frac > 0.5 {
             bar_colour = "yellow";
         } else {
             bar_colour = "white";
@@ -21,7 +32,7 @@ pub fn budget_pbar(summary: BudgetSummary) -> ProgressBar {
             "{{msg:<30.{bar_colour}}} {{wide_bar:.{bar_colour}}} {{pos:>6.{bar_colour}}}/{{len:>6}} ({{percent:>3.{bar_colour}}} %)"
         );
 
-        pbar.set_message(format!("{}", summary.name()));
+        pbar.set_message(budget_name(&summary));
         pbar.set_style(ProgressStyle::default_bar().template(&template));
 
         pbar.set_position(summary.progress_rounded());
@@ -35,7 +46,7 @@ pub fn budget_pbar(summary: BudgetSummary) -> ProgressBar {
             "{{msg:<30.{bar_colour}}} {{wide_bar:.{bar_colour}}} {{pos:>6.{bar_colour}}}/  None ({{percent:>3.{bar_colour}}} %)"
         );
 
-        pbar.set_message(format!("{}", summary.name()));
+        pbar.set_message(budget_name(&summary));
         pbar.set_style(ProgressStyle::default_bar().template(&template));
 
         pbar.set_position(summary.progress_rounded());
@@ -43,3 +54,12 @@ pub fn budget_pbar(summary: BudgetSummary) -> ProgressBar {
         pbar
     }
 }
+
+/// Name the budget, marking it if any of its amounts were converted from another currency
+fn budget_name(summary: &BudgetSummary) -> String {
+    if summary.is_converted() {
+        format!("{} (converted)", summary.name())
+    } else {
+        summary.name().to_string()
+    }
+}
@@ -1,8 +1,16 @@
 //! Top level CLI command
 
+use super::{NetWorthOpts, OutputFormat, PostScheduledOpts, SumOpts};
 use crate::config::default_cfg_file;
 use clap::Parser;
-use homebank_db::{category::{QueryBudget, QueryReview}, QueryOpts, QueryTransactions};
+use homebank_db::{
+    account::{QueryBalance, QueryRegister},
+    assignment::QueryAutoAssign,
+    category::{QueryBudget, QueryReview, QueryTrend},
+    template::QueryUpcoming,
+    transaction::QueryCashflow,
+    QueryOpts,
+};
 use lazy_static::lazy_static;
 use std::path::{Path, PathBuf};
 
@@ -21,6 +29,28 @@ pub struct CliOpts {
     )]
     pub path: PathBuf,
 
+    /// Fail if any entry in the HomeBank file cannot be parsed, instead of skipping it
+    #[clap(long = "strict")]
+    pub strict: bool,
+
+    /// Format to print the results in
+    #[clap(
+        long = "format",
+        global = true,
+        default_value = "table",
+        possible_values = &["table", "csv", "tsv", "json", "jsonl"]
+    )]
+    pub format: OutputFormat,
+
+    /// Only print these columns, separated by commas, in this order
+    #[clap(
+        long = "columns",
+        global = true,
+        value_name = "columns",
+        use_value_delimiter = true
+    )]
+    pub columns: Option<Vec<String>>,
+
     /// Optional subcommand
     #[clap(subcommand)]
     pub subcmd: Option<SubCommand>,
@@ -31,6 +61,9 @@ impl CliOpts {
     pub fn new(path: &Path, subcmd: Option<SubCommand>) -> Self {
         Self {
             path: path.to_path_buf(),
+            strict: false,
+            format: OutputFormat::default(),
+            columns: None,
             subcmd,
         }
     }
@@ -40,6 +73,21 @@ impl CliOpts {
         &self.path
     }
 
+    /// Check if the HomeBank file should be parsed strictly
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Retrieve the format to print the results in
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Retrieve the columns to print, if only some of them should be
+    pub fn columns(&self) -> Option<&[String]> {
+        self.columns.as_deref()
+    }
+
     /// Retrieve the subcommand given, if any
     pub fn subcommand(&self) -> Option<&SubCommand> {
         match &self.subcmd {
@@ -53,6 +101,9 @@ impl Default for CliOpts {
     fn default() -> Self {
         CliOpts {
             path: default_cfg_file(),
+            strict: false,
+            format: OutputFormat::default(),
+            columns: None,
             subcmd: None,
         }
     }
@@ -66,13 +117,44 @@ pub enum SubCommand {
 
     /// Calculate a sum of transactions in a query.
     #[clap(visible_alias = "s")]
-    Sum(QueryTransactions),
+    Sum(SumOpts),
 
-    /// Print a tab-separated table of each category and the sum of its transactions.
+    /// Print a table of each category and the sum of its transactions.
     #[clap(visible_alias = "r")]
     Review(QueryReview),
 
+    /// Print the cleared, current, and future balance of each account, with subtotals for each group.
+    #[clap(visible_alias = "bal")]
+    Balance(QueryBalance),
+
+    /// Print the transactions in an account with a running balance, like HomeBank's register.
+    #[clap(visible_alias = "reg")]
+    Register(QueryRegister),
+
+    /// Print the assets, liabilities, and net worth at the end of each period.
+    #[clap(visible_alias = "nw")]
+    Networth(NetWorthOpts),
+
+    /// Print the income, expenses, net, and savings rate of each period, leaving out transfers.
+    #[clap(visible_alias = "cf")]
+    Cashflow(QueryCashflow),
+
+    /// Print a table of each category's sum in each period, like HomeBank's "Trend time" report.
+    #[clap(visible_alias = "tr")]
+    Trend(QueryTrend),
+
     /// Look at your category budgets.
     #[clap(visible_alias = "b")]
     Budget(QueryBudget),
+
+    /// Forecast the scheduled transactions coming up, and the balance of each account after them.
+    #[clap(visible_alias = "u")]
+    Upcoming(QueryUpcoming),
+
+    /// Show the uncategorized transactions that the automatic assignment rules would categorize.
+    #[clap(visible_alias = "a")]
+    Assign(QueryAutoAssign),
+
+    /// Add the scheduled transactions that are due to the HomeBank file.
+    PostScheduled(PostScheduledOpts),
 }
@@ -0,0 +1,24 @@
+//! Choose the currency that amounts are displayed in.
+
+use homebank_db::{Currency, HomeBankDb};
+
+/// Find the currency to display amounts in, from its ISO code or the base currency of the database.
+///
+/// Fails if an ISO code is given that doesn't match any currency in the database,
+/// or if amounts in any account can't be converted into the currency, so that no total is left incomplete.
+pub fn target_currency<'a>(
+    db: &'a HomeBankDb,
+    iso: Option<&str>,
+) -> Result<Option<&'a Currency>, anyhow::Error> {
+    let key = db.currency_key(iso)?;
+
+    Ok(db.currencies().get(&key))
+}
+
+/// Describe a total that was converted from other currencies, to print next to it.
+pub fn converted_note(currency: Option<&Currency>) -> String {
+    match currency {
+        Some(curr) => format!("(converted to {})", curr.iso()),
+        None => "(converted)".to_string(),
+    }
+}
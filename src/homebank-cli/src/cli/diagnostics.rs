@@ -0,0 +1,44 @@
+//! Summarize the entries in the HomeBank file that could not be parsed.
+
+use homebank_db::ParseDiagnostic;
+
+/// Describe the kind of entry stored in an XML element.
+fn element_noun(element: &str, count: usize) -> String {
+    let (singular, plural) = match element {
+        "homebank" => ("database header", "database headers"),
+        "properties" => ("database property set", "database property sets"),
+        "cur" => ("currency", "currencies"),
+        "grp" => ("account group", "account groups"),
+        "account" => ("account", "accounts"),
+        "pay" => ("payee", "payees"),
+        "cat" => ("category", "categories"),
+        "tag" => ("tag", "tags"),
+        "asg" => ("assignment rule", "assignment rules"),
+        "fav" => ("template", "templates"),
+        "ope" => ("transaction", "transactions"),
+        _ => ("entry", "entries"),
+    };
+
+    if count == 1 {
+        singular.to_string()
+    } else {
+        plural.to_string()
+    }
+}
+
+/// Summarize the skipped entries, one line per kind of entry, in the order they first appear in the file.
+pub fn skipped_summary(diagnostics: &[ParseDiagnostic]) -> Vec<String> {
+    let mut counts: Vec<(&str, usize)> = vec![];
+
+    for d in diagnostics {
+        match counts.iter_mut().find(|(el, _)| *el == d.element()) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((d.element(), 1)),
+        }
+    }
+
+    counts
+        .iter()
+        .map(|&(el, n)| format!("{n} {} skipped", element_noun(el, n)))
+        .collect()
+}
@@ -2,6 +2,19 @@
 
 pub mod budget;
 pub mod command;
+pub mod currency;
+pub mod diagnostics;
+pub mod networth;
+pub mod output;
+pub mod post_scheduled;
+pub mod schema;
+pub mod sum;
 
 pub use budget::budget_pbar;
 pub use command::{CliOpts, SubCommand};
+pub use currency::{converted_note, target_currency};
+pub use diagnostics::skipped_summary;
+pub use networth::NetWorthOpts;
+pub use output::{Field, OutputFormat, Records};
+pub use post_scheduled::PostScheduledOpts;
+pub use sum::SumOpts;
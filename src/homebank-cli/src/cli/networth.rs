@@ -0,0 +1,28 @@
+//! Options for reporting net worth over time.
+
+use clap::Parser;
+use homebank_db::account::QueryNetWorth;
+
+/// Options for reporting net worth over time.
+#[derive(Debug, Parser)]
+pub struct NetWorthOpts {
+    /// The periods to report the net worth for.
+    #[clap(flatten)]
+    query: QueryNetWorth,
+
+    /// Also show the net worth of the accounts in each group
+    #[clap(short = 'g', long = "by-group")]
+    by_group: bool,
+}
+
+impl NetWorthOpts {
+    /// Retrieve the query for the net worth
+    pub fn query(&self) -> &QueryNetWorth {
+        &self.query
+    }
+
+    /// Check if the net worth of each group is shown as well
+    pub fn by_group(&self) -> bool {
+        self.by_group
+    }
+}
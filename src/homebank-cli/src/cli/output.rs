@@ -0,0 +1,528 @@
+//! Print the results of a subcommand as a table, or in a structured format for scripts.
+
+use console::{measure_text_width, style, truncate_str, Term};
+use homebank_db::{Currency, Money};
+use std::{
+    fmt,
+    io::{self, Write},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Format that the results of a subcommand are printed in.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum OutputFormat {
+    /// Columns aligned for reading in the terminal.
+    #[default]
+    Table,
+
+    /// Comma-separated values, with a header row.
+    Csv,
+
+    /// Tab-separated values, with a header row.
+    Tsv,
+
+    /// A JSON array with an object for each row.
+    Json,
+
+    /// A JSON object for each row, one per line.
+    Jsonl,
+}
+
+/// Errors when choosing how to print [`Records`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OutputError {
+    /// When the name isn't one of the supported formats.
+    #[error("Invalid output format `{0}`. Options are 'table', 'csv', 'tsv', 'json', or 'jsonl'.")]
+    InvalidFormat(String),
+
+    /// When a column is selected that the subcommand doesn't print.
+    #[error("Invalid column `{0}`. Options are {1}.")]
+    InvalidColumn(String, String),
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" => Ok(OutputFormat::Jsonl),
+            _ => Err(OutputError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Json => "json",
+            OutputFormat::Jsonl => "jsonl",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A single value in a row of [`Records`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Field {
+    /// No value, which is `null` in JSON and empty otherwise.
+    Null,
+
+    /// Text, such as a name or a date.
+    Text(String),
+
+    /// A whole number, such as a count.
+    Int(i64),
+
+    /// An amount of money, rounded to a number of decimals.
+    /// This is a number in JSON.
+    Amount(Money, usize),
+
+    /// An amount of money in a [`Currency`], which the table shows with its symbol and separators.
+    /// This is a number in JSON, and is rounded to the currency's decimals everywhere else.
+    InCurrency(Money, Currency),
+
+    /// A number that isn't money, such as a percentage, rounded to a number of decimals.
+    Number(f64, usize),
+
+    /// A yes or no value.
+    /// This is `true` or `false` in every format except the table, where the label is shown if it is set.
+    Flag(bool, String),
+
+    /// A value that needs attention, such as an overspent budget.
+    /// It is highlighted in red in the table, and otherwise printed like the value inside.
+    Alert(Box<Field>),
+}
+
+impl Field {
+    /// Create a text field
+    pub fn text(s: &str) -> Self {
+        Field::Text(s.to_string())
+    }
+
+    /// Create a text field, or an empty field if there is no text
+    pub fn opt_text(s: Option<&str>) -> Self {
+        s.map_or(Field::Null, Field::text)
+    }
+
+    /// Create a yes or no field with the label it shows in the table
+    pub fn flag(set: bool, label: &str) -> Self {
+        Field::Flag(set, label.to_string())
+    }
+
+    /// Highlight the field if it needs attention
+    pub fn alert_if(self, alert: bool) -> Self {
+        if alert {
+            Field::Alert(Box::new(self))
+        } else {
+            self
+        }
+    }
+
+    /// Check if the field is numeric, so it can be right-aligned in the table
+    fn is_numeric(&self) -> bool {
+        match self {
+            Field::Int(_) | Field::Amount(..) | Field::InCurrency(..) | Field::Number(..) => true,
+            Field::Alert(field) => field.is_numeric(),
+            _ => false,
+        }
+    }
+
+    /// Render the field as plain text, for CSV and TSV
+    fn plain(&self) -> String {
+        match self {
+            Field::Null => String::new(),
+            Field::Text(s) => s.clone(),
+            Field::Int(n) => n.to_string(),
+            Field::Amount(amount, decimals) => format!("{amount:.decimals$}"),
+            Field::InCurrency(amount, curr) => {
+                let decimals = curr.decimal_len();
+                format!("{amount:.decimals$}")
+            }
+            // adding zero turns `-0.0` into `0.0`
+            Field::Number(value, decimals) => format!("{:.decimals$}", value + 0.0),
+            Field::Flag(set, _) => set.to_string(),
+            Field::Alert(field) => field.plain(),
+        }
+    }
+
+    /// Render the field for a table in the terminal, without highlighting
+    fn cell(&self) -> String {
+        match self {
+            Field::Flag(true, label) => label.clone(),
+            Field::Flag(false, _) => String::new(),
+            Field::InCurrency(amount, curr) => curr.format(*amount),
+            Field::Alert(field) => field.cell(),
+            _ => self.plain(),
+        }
+    }
+
+    /// Check if the field should be highlighted in the table, because it needs attention or is a negative amount
+    fn is_highlighted(&self) -> bool {
+        match self {
+            Field::Alert(_) => true,
+            Field::InCurrency(amount, curr) => curr.round(*amount).is_negative(),
+            _ => false,
+        }
+    }
+
+    /// Render the field as a JSON value
+    fn json(&self) -> String {
+        match self {
+            Field::Null => "null".to_string(),
+            Field::Text(s) => serde_json::to_string(s).unwrap_or_else(|_| "null".to_string()),
+            Field::Number(value, _) if !value.is_finite() => "null".to_string(),
+            Field::Flag(set, _) => set.to_string(),
+            Field::Alert(field) => field.json(),
+            // amounts and numbers are already valid JSON numbers
+            _ => self.plain(),
+        }
+    }
+}
+
+/// Rows of results with named columns, ready to print in any [`OutputFormat`].
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Records {
+    /// Names of the columns, which are the keys in JSON.
+    columns: Vec<String>,
+
+    /// Values in each row, in the same order as the columns.
+    rows: Vec<Vec<Field>>,
+
+    /// Column that is truncated so the table fits the width of the terminal.
+    truncated: Option<usize>,
+}
+
+impl Records {
+    /// Create an empty set of records with the given columns
+    pub fn new(columns: &[&str]) -> Self {
+        Self::with_columns(columns.iter().map(|c| c.to_string()).collect())
+    }
+
+    /// Create an empty set of records with columns that are only known at runtime, like periods
+    pub fn with_columns(columns: Vec<String>) -> Self {
+        Self {
+            columns,
+            rows: vec![],
+            truncated: None,
+        }
+    }
+
+    /// Truncate a column, like a memo, so the table fits the width of the terminal
+    pub fn truncate_to_fit(mut self, column: &str) -> Self {
+        self.truncated = self.columns.iter().position(|col| col == column);
+        self
+    }
+
+    /// Keep only the given columns, in the given order
+    pub fn select(self, columns: &[String]) -> Result<Self, OutputError> {
+        let indices = columns
+            .iter()
+            .map(|name| {
+                self.columns.iter().position(|col| col == name).ok_or_else(|| {
+                    let options: Vec<String> =
+                        self.columns.iter().map(|col| format!("'{col}'")).collect();
+                    OutputError::InvalidColumn(name.to_string(), options.join(", "))
+                })
+            })
+            .collect::<Result<Vec<usize>, OutputError>>()?;
+
+        Ok(Self {
+            columns: indices.iter().map(|&i| self.columns[i].clone()).collect(),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| indices.iter().map(|&i| row[i].clone()).collect())
+                .collect(),
+            truncated: self
+                .truncated
+                .and_then(|t| indices.iter().position(|&i| i == t)),
+        })
+    }
+
+    /// Retrieve the names of the columns
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Retrieve the values in each row
+    pub fn rows(&self) -> &[Vec<Field>] {
+        &self.rows
+    }
+
+    /// Add a row, with a value for each column
+    pub fn push(&mut self, row: Vec<Field>) {
+        debug_assert_eq!(self.columns.len(), row.len());
+        self.rows.push(row);
+    }
+
+    /// Print the records to stdout
+    pub fn print(&self, format: OutputFormat) -> io::Result<()> {
+        let stdout = io::stdout();
+        let mut handle = stdout.lock();
+
+        // only the table needs to fit the terminal, and only when it is printed to one
+        let max_width = match format {
+            OutputFormat::Table => Term::stdout().size_checked().map(|(_, cols)| cols as usize),
+            _ => None,
+        };
+
+        let result = match format {
+            OutputFormat::Table => self.write_table(&mut handle, max_width),
+            _ => self.write(format, &mut handle),
+        };
+        match result {
+            // stop quietly when piped into something like `head`
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Write the records in the given format
+    pub fn write<W: Write>(&self, format: OutputFormat, w: &mut W) -> io::Result<()> {
+        match format {
+            OutputFormat::Table => self.write_table(w, None),
+            OutputFormat::Csv => self.write_separated(w, ",", csv_escape),
+            OutputFormat::Tsv => self.write_separated(w, "\t", tsv_escape),
+            OutputFormat::Json => {
+                let objects: Vec<String> =
+                    self.rows.iter().map(|row| self.json_object(row)).collect();
+                if objects.is_empty() {
+                    writeln!(w, "[]")
+                } else {
+                    writeln!(w, "[\n  {}\n]", objects.join(",\n  "))
+                }
+            }
+            OutputFormat::Jsonl => {
+                for row in &self.rows {
+                    writeln!(w, "{}", self.json_object(row))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Write the records with columns aligned, and numbers right-aligned.
+    ///
+    /// If the table is wider than `max_width`, the truncated column is shortened, down to the width of its name.
+    fn write_table<W: Write>(&self, w: &mut W, max_width: Option<usize>) -> io::Result<()> {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(Field::cell).collect())
+            .collect();
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                cells
+                    .iter()
+                    .map(|row| measure_text_width(&row[i]))
+                    .chain([measure_text_width(col)])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        if let (Some(max_width), Some(i)) = (max_width, self.truncated) {
+            let table_width = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+            let overflow = table_width.saturating_sub(max_width);
+            widths[i] = widths[i]
+                .saturating_sub(overflow)
+                .max(measure_text_width(&self.columns[i]));
+        }
+        let numeric: Vec<bool> = (0..self.columns.len())
+            .map(|i| self.rows.iter().any(|row| row[i].is_numeric()))
+            .collect();
+
+        let header: Vec<String> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| pad(col, widths[i], numeric[i]))
+            .collect();
+        writeln!(w, "{}", header.join("  ").trim_end())?;
+
+        for (row, row_cells) in self.rows.iter().zip(cells) {
+            let line: Vec<String> = row
+                .iter()
+                .zip(row_cells)
+                .enumerate()
+                .map(|(i, (field, cell))| {
+                    let cell = if measure_text_width(&cell) > widths[i] {
+                        truncate_str(&cell, widths[i], "…").to_string()
+                    } else {
+                        cell
+                    };
+                    let padded = pad(&cell, widths[i], numeric[i]);
+                    if field.is_highlighted() {
+                        style(padded).red().to_string()
+                    } else {
+                        padded
+                    }
+                })
+                .collect();
+            writeln!(w, "{}", line.join("  ").trim_end())?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the records with a header row, separating the values in each row
+    fn write_separated<W: Write>(
+        &self,
+        w: &mut W,
+        separator: &str,
+        escape: fn(&str) -> String,
+    ) -> io::Result<()> {
+        let header: Vec<String> = self.columns.iter().map(|col| escape(col)).collect();
+        writeln!(w, "{}", header.join(separator))?;
+
+        for row in &self.rows {
+            let values: Vec<String> = row.iter().map(|field| escape(&field.plain())).collect();
+            writeln!(w, "{}", values.join(separator))?;
+        }
+
+        Ok(())
+    }
+
+    /// Render a row as a JSON object, keeping the keys in the same order as the columns
+    fn json_object(&self, row: &[Field]) -> String {
+        let pairs: Vec<String> = self
+            .columns
+            .iter()
+            .zip(row)
+            .map(|(col, field)| {
+                let key = serde_json::to_string(col).unwrap_or_default();
+                format!("{key}:{}", field.json())
+            })
+            .collect();
+
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// Pad a cell to the width of its column
+fn pad(cell: &str, width: usize, right_align: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(measure_text_width(cell)));
+    if right_align {
+        format!("{padding}{cell}")
+    } else {
+        format!("{cell}{padding}")
+    }
+}
+
+/// Quote a CSV value if it contains a separator, quote, or line break
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Replace the tabs and line breaks in a TSV value, since they can't be escaped
+fn tsv_escape(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Records {
+        let mut records = Records::new(&["name", "amount", "converted"]);
+        records.push(vec![
+            Field::text("Food, \"fresh\""),
+            Field::Amount(Money::new(-1250, 2), 2),
+            Field::flag(true, "(converted)"),
+        ]);
+        records.push(vec![
+            Field::Null,
+            Field::Amount(Money::from(3), 2).alert_if(true),
+            Field::flag(false, "(converted)"),
+        ]);
+
+        records
+    }
+
+    #[track_caller]
+    fn check_write(format: OutputFormat, expected: &str) {
+        let mut observed = vec![];
+        console::set_colors_enabled(false);
+        example().write(format, &mut observed).unwrap();
+
+        assert_eq!(expected, String::from_utf8(observed).unwrap());
+    }
+
+    #[test]
+    fn parse_formats() {
+        assert_eq!(Ok(OutputFormat::Jsonl), OutputFormat::from_str("jsonl"));
+        assert_eq!(
+            Err(OutputError::InvalidFormat("xml".to_string())),
+            OutputFormat::from_str("xml")
+        );
+    }
+
+    #[test]
+    fn write_csv() {
+        let expected = "name,amount,converted\n\"Food, \"\"fresh\"\"\",-12.50,true\n,3.00,false\n";
+
+        check_write(OutputFormat::Csv, expected);
+    }
+
+    #[test]
+    fn write_json() {
+        let expected = "[\n  {\"name\":\"Food, \\\"fresh\\\"\",\"amount\":-12.50,\"converted\":true},\n  {\"name\":null,\"amount\":3.00,\"converted\":false}\n]\n";
+
+        check_write(OutputFormat::Json, expected);
+    }
+
+    #[test]
+    fn write_table() {
+        let expected = "name           amount  converted\nFood, \"fresh\"  -12.50  (converted)\n                 3.00\n";
+
+        check_write(OutputFormat::Table, expected);
+    }
+
+    #[test]
+    fn select_columns() {
+        let columns = vec!["converted".to_string(), "name".to_string()];
+        let observed = example().select(&columns).unwrap();
+
+        assert_eq!(&columns, observed.columns());
+        assert_eq!(
+            vec![Field::flag(false, "(converted)"), Field::Null],
+            observed.rows()[1]
+        );
+        assert_eq!(
+            Err(OutputError::InvalidColumn(
+                "payee".to_string(),
+                "'name', 'amount', 'converted'".to_string()
+            )),
+            example().select(&["payee".to_string()])
+        );
+    }
+
+    #[test]
+    fn truncate_table_to_fit() {
+        let mut records = Records::new(&["memo", "amount"]).truncate_to_fit("memo");
+        records.push(vec![
+            Field::text("a very long memo"),
+            Field::InCurrency(Money::new(-123450, 2), Currency::default()),
+        ]);
+        let mut observed = vec![];
+        console::set_colors_enabled(false);
+        records.write_table(&mut observed, Some(20)).unwrap();
+        let expected = "memo          amount\na very…  -1 234.50 $\n";
+
+        assert_eq!(expected, String::from_utf8(observed).unwrap());
+    }
+}
@@ -0,0 +1,18 @@
+//! Options for posting scheduled transactions into the HomeBank database.
+
+use clap::Parser;
+
+/// Options for posting scheduled transactions into the HomeBank database.
+#[derive(Debug, Parser)]
+pub struct PostScheduledOpts {
+    /// Only print the transactions that would be posted, without changing the HomeBank file
+    #[clap(short = 'n', long = "dry-run")]
+    dry_run: bool,
+}
+
+impl PostScheduledOpts {
+    /// Check if the HomeBank file should be left unchanged
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
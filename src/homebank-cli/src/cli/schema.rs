@@ -0,0 +1,834 @@
+//! The columns that each subcommand prints, with keys in the database resolved to names.
+//!
+//! The column names are the keys of each object in the JSON formats, so they should only ever be added to.
+
+use super::{converted_note, Field, OutputFormat, Records};
+use homebank_db::{
+    account::{
+        balance_query::{Balances, GroupBalance},
+        networth_query::NetWorthSummary,
+        register_query::RegisterEntry,
+    },
+    category::{
+        budget_query::{BudgetMatrixRow, BudgetProjection, BudgetSummary},
+        review_query::CategoryRollup,
+        trend_query::Trend,
+    },
+    tag::TagSummary,
+    template::UpcomingTransaction,
+    transaction::{cashflow_query::Cashflow, TransactionGroup},
+    Account, Assignment, Category, Currency, Group, HomeBankDb, Money, PayMode, Payee, Template,
+    Transaction, TransactionStatus,
+};
+
+/// Retrieve the number of decimals used by an account's currency
+fn account_decimals(db: &HomeBankDb, account: usize, decimals: usize) -> usize {
+    db.accounts()
+        .get(&account)
+        .and_then(|acct| db.currencies().get(&acct.currency()))
+        .map_or(decimals, |curr| curr.decimal_len())
+}
+
+/// Show an amount in its account's currency, if the account has one
+fn account_amount(db: &HomeBankDb, account: usize, amount: Money, decimals: usize) -> Field {
+    match db
+        .accounts()
+        .get(&account)
+        .and_then(|acct| db.currencies().get(&acct.currency()))
+    {
+        Some(curr) => Field::InCurrency(amount, curr.clone()),
+        None => Field::Amount(amount, decimals),
+    }
+}
+
+/// Mark whether an amount was converted from another currency
+fn converted(is_converted: bool, currency: Option<&Currency>) -> Field {
+    Field::flag(is_converted, &converted_note(currency))
+}
+
+/// Retrieve the names of a transaction's categories, joining the lines of a split transaction
+fn category_names(tr: &Transaction, db: &HomeBankDb) -> Field {
+    let names: Vec<String> = tr.category_names(db).into_iter().flatten().collect();
+
+    if names.is_empty() {
+        Field::Null
+    } else {
+        Field::Text(names.join(", "))
+    }
+}
+
+/// Retrieve the memo of a transaction, or of a single line of a split transaction
+fn line_memo(tr: &Transaction) -> Field {
+    match tr.memos().as_slice() {
+        [Some(split_memo)] if tr.is_split() && !split_memo.is_empty() => Field::text(split_memo),
+        _ => Field::opt_text(tr.memo().as_deref()),
+    }
+}
+
+/// Retrieve the status of a transaction, leaving it empty if there is none
+fn status(tr: &Transaction) -> Field {
+    match tr.status() {
+        TransactionStatus::None => Field::Null,
+        st => Field::Text(st.to_string()),
+    }
+}
+
+/// Columns: `date`, `account`, `payee`, `category`, `memo`, `info`, `tags`, `pay_mode`, `status`, `type`, `split`, `amount`.
+///
+/// The categories of a split transaction are separated by `, ` and its tags by spaces.
+/// In the table, amounts are shown in the currency of their account, and memos are truncated to fit the terminal.
+pub fn transactions(transactions: &[Transaction], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "date", "account", "payee", "category", "memo", "info", "tags", "pay_mode", "status",
+        "type", "split", "amount",
+    ])
+    .truncate_to_fit("memo");
+
+    for tr in transactions {
+        records.push(vec![
+            Field::Text(tr.date().to_string()),
+            Field::opt_text(tr.account_name(db).as_deref()),
+            Field::opt_text(tr.payee_name(db).as_deref()),
+            category_names(tr, db),
+            Field::opt_text(tr.memo().as_deref()),
+            Field::opt_text(tr.info().as_deref()),
+            Field::opt_text(tr.tags().as_ref().map(|tags| tags.join(" ")).as_deref()),
+            Field::Text(tr.pay_mode().to_string()),
+            status(tr),
+            Field::Text(tr.ttype().to_string()),
+            Field::flag(tr.is_split(), "split"),
+            account_amount(db, tr.account(), *tr.total(), decimals),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `name`, `category`, `pay_mode`.
+pub fn payees(payees: &[Payee], db: &HomeBankDb) -> Records {
+    let mut records = Records::new(&["name", "category", "pay_mode"]);
+
+    for payee in payees {
+        let category = payee
+            .category()
+            .and_then(|idx| db.categories().get(&idx))
+            .map(|cat| cat.full_name(db));
+        let pay_mode = payee
+            .paymode()
+            .and_then(|pm| PayMode::try_from(pm).ok())
+            .map(|pm| pm.to_string());
+
+        records.push(vec![
+            Field::text(payee.name()),
+            Field::opt_text(category.as_deref()),
+            Field::opt_text(pay_mode.as_deref()),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `iso`, `name`, `decimals`, `rate`.
+///
+/// The rate is how many units of the currency one unit of the base currency is worth.
+pub fn currencies(currencies: &[Currency]) -> Records {
+    let mut records = Records::new(&["iso", "name", "decimals", "rate"]);
+
+    for curr in currencies {
+        let rate = curr.conversion_rate().to_string().parse().unwrap_or(0.0);
+        records.push(vec![
+            Field::text(curr.iso()),
+            Field::text(curr.name()),
+            Field::Int(curr.decimal_len() as i64),
+            Field::Number(rate, 6),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `name`, `parent`, `income`.
+///
+/// The name of a subcategory includes its parent category, like `Food:Groceries`.
+pub fn categories(categories: &[Category], db: &HomeBankDb) -> Records {
+    let mut records = Records::new(&["name", "parent", "income"]);
+
+    for cat in categories {
+        records.push(vec![
+            Field::Text(cat.full_name(db)),
+            Field::opt_text(cat.parent_name(db)),
+            Field::flag(cat.is_income(), "income"),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `name`, `type`, `group`, `institution`, `currency`, `initial`, `closed`.
+pub fn accounts(accounts: &[Account], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "name",
+        "type",
+        "group",
+        "institution",
+        "currency",
+        "initial",
+        "closed",
+    ]);
+
+    for acct in accounts {
+        let group = acct
+            .group()
+            .and_then(|idx| db.groups().get(&idx))
+            .map(|grp| grp.name());
+        let currency = db.currencies().get(&acct.currency());
+        let decimals = currency.map_or(decimals, |curr| curr.decimal_len());
+        let institution = Some(acct.institution()).filter(|inst| !inst.is_empty());
+
+        records.push(vec![
+            Field::text(acct.name()),
+            Field::Text(acct.atype().to_string()),
+            Field::opt_text(group),
+            Field::opt_text(institution),
+            Field::opt_text(currency.map(|curr| curr.iso())),
+            Field::Amount(acct.initial_amount(), decimals),
+            Field::flag(acct.is_closed(), "closed"),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `name`.
+pub fn groups(groups: &[Group]) -> Records {
+    let mut records = Records::new(&["name"]);
+
+    for grp in groups {
+        records.push(vec![Field::text(grp.name())]);
+    }
+
+    records
+}
+
+/// Columns: `search`, `field`, `payee`, `category`, `pay_mode`, `exact`, `regex`.
+///
+/// `field` is `memo` or `payee`, and the other columns are what the rule assigns.
+pub fn assignments(assignments: &[Assignment], db: &HomeBankDb) -> Records {
+    let mut records = Records::new(&[
+        "search", "field", "payee", "category", "pay_mode", "exact", "regex",
+    ]);
+
+    for asg in assignments {
+        let payee = asg
+            .payee()
+            .and_then(|idx| db.payees().get(&idx))
+            .map(|payee| payee.name());
+        let category = asg
+            .category()
+            .and_then(|idx| db.categories().get(&idx))
+            .map(|cat| cat.full_name(db));
+
+        records.push(vec![
+            Field::text(asg.search()),
+            Field::Text(asg.field().to_string()),
+            Field::opt_text(payee),
+            Field::opt_text(category.as_deref()),
+            Field::opt_text(asg.paymode().map(|pm| pm.to_string()).as_deref()),
+            Field::flag(asg.is_exact(), "exact"),
+            Field::flag(asg.is_regex(), "regex"),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `tag`, `count`, `total`, `status`.
+///
+/// `status` is `missing` for tags that aren't in the tag list, `unused` for tags that no transaction has, and empty otherwise.
+pub fn tags(tags: &[TagSummary], decimals: usize) -> Records {
+    let mut records = Records::new(&["tag", "count", "total", "status"]);
+
+    for summary in tags {
+        let status = if summary.is_missing() {
+            Field::text("missing")
+        } else if summary.is_unused() {
+            Field::text("unused")
+        } else {
+            Field::Null
+        };
+
+        records.push(vec![
+            Field::text(summary.name()),
+            Field::Int(summary.count() as i64),
+            Field::Amount(summary.total(), decimals),
+            status,
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `name`, `account`, `payee`, `category`, `amount`, `scheduled`, `next_date`, `every`, `unit`, `limit`.
+///
+/// The schedule columns are empty for templates that are only favourites.
+pub fn templates(templates: &[Template], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "name",
+        "account",
+        "payee",
+        "category",
+        "amount",
+        "scheduled",
+        "next_date",
+        "every",
+        "unit",
+        "limit",
+    ]);
+
+    for tmpl in templates {
+        let tr = tmpl.transaction();
+        let decimals = account_decimals(db, tr.account(), decimals);
+        let (next_date, every, unit, limit) = if tmpl.is_scheduled() {
+            (
+                Field::Text(tmpl.next_date().to_string()),
+                Field::Int(tmpl.every().into()),
+                Field::Text(tmpl.unit().to_string()),
+                tmpl.limit().map_or(Field::Null, |n| Field::Int(n.into())),
+            )
+        } else {
+            (Field::Null, Field::Null, Field::Null, Field::Null)
+        };
+
+        records.push(vec![
+            Field::text(tmpl.name()),
+            Field::opt_text(tmpl.account_name(db).as_deref()),
+            Field::opt_text(tmpl.payee_name(db).as_deref()),
+            category_names(tr, db),
+            Field::Amount(*tr.total(), decimals),
+            Field::flag(tmpl.is_scheduled(), "scheduled"),
+            next_date,
+            every,
+            unit,
+            limit,
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `sum`, `currency`, `converted`.
+pub fn sum(
+    sum: Money,
+    is_converted: bool,
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&["sum", "currency", "converted"]);
+    records.push(vec![
+        Field::Amount(sum, decimals),
+        Field::opt_text(currency.map(|curr| curr.iso())),
+        converted(is_converted, currency),
+    ]);
+
+    records
+}
+
+/// Columns: `group`, `count`, `sum`, `average`, `min`, `max`, `converted`.
+///
+/// `group` is empty for transactions without a payee, category, or tag.
+pub fn sum_groups(
+    groups: &[TransactionGroup],
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&[
+        "group",
+        "count",
+        "sum",
+        "average",
+        "min",
+        "max",
+        "converted",
+    ]);
+
+    for grp in groups {
+        records.push(vec![
+            Field::opt_text(grp.name()),
+            Field::Int(grp.count() as i64),
+            Field::Amount(grp.sum(), decimals),
+            Field::Amount(grp.average(), decimals),
+            Field::Amount(grp.min(), decimals),
+            Field::Amount(grp.max(), decimals),
+            converted(grp.is_converted(), currency),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `group`, `account`, `cleared`, `current`, `future`, `converted`.
+///
+/// Each group ends with a row for its total, where `account` is `Total`.
+/// The last row is the total across every group, where `group` is `Total`.
+pub fn balances(groups: &[GroupBalance], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "group",
+        "account",
+        "cleared",
+        "current",
+        "future",
+        "converted",
+    ]);
+    let base = db.base_currency();
+    let row = |group: Field, account: Field, bal: &Balances, decimals: usize, conv: Field| {
+        vec![
+            group,
+            account,
+            Field::Amount(bal.cleared(), decimals),
+            Field::Amount(bal.current(), decimals),
+            Field::Amount(bal.future(), decimals),
+            conv,
+        ]
+    };
+
+    let mut total = Balances::default();
+    let mut any_converted = false;
+    for grp in groups {
+        let grp_name = Field::opt_text(grp.name());
+        for acct in grp.accounts() {
+            let decimals = db
+                .currencies()
+                .get(&acct.currency())
+                .map_or(decimals, |curr| curr.decimal_len());
+            records.push(row(
+                grp_name.clone(),
+                Field::text(acct.name()),
+                acct.balances(),
+                decimals,
+                converted(false, base),
+            ));
+        }
+
+        records.push(row(
+            grp_name,
+            Field::text("Total"),
+            grp.total(),
+            decimals,
+            converted(grp.is_converted(), base),
+        ));
+        total = total + *grp.total();
+        any_converted |= grp.is_converted();
+    }
+
+    records.push(row(
+        Field::text("Total"),
+        Field::Null,
+        &total,
+        decimals,
+        converted(any_converted, base),
+    ));
+
+    records
+}
+
+/// Columns: `account`, `date`, `payee`, `category`, `memo`, `status`, `amount`, `balance`.
+pub fn register(entries: &[RegisterEntry], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "account", "date", "payee", "category", "memo", "status", "amount", "balance",
+    ]);
+
+    for entry in entries {
+        let tr = entry.transaction();
+        let decimals = account_decimals(db, tr.account(), decimals);
+        records.push(vec![
+            Field::opt_text(tr.account_name(db).as_deref()),
+            Field::Text(tr.date().to_string()),
+            Field::opt_text(tr.payee_name(db).as_deref()),
+            category_names(tr, db),
+            line_memo(tr),
+            status(tr),
+            Field::Amount(*tr.total(), decimals),
+            Field::Amount(entry.balance(), decimals),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `date`, `assets`, `liabilities`, `net`, `converted`.
+///
+/// With `by_group`, a `group` column follows `date`, and each period ends with a row for its total, where `group` is `Total`.
+/// Accounts that aren't in a group have an empty `group`.
+pub fn networth(
+    summaries: &[NetWorthSummary],
+    by_group: bool,
+    db: &HomeBankDb,
+    decimals: usize,
+) -> Records {
+    let mut records = if by_group {
+        Records::new(&["date", "group", "assets", "liabilities", "net", "converted"])
+    } else {
+        Records::new(&["date", "assets", "liabilities", "net", "converted"])
+    };
+
+    for summary in summaries {
+        let date = Field::Text(summary.date().to_string());
+        let conv = converted(summary.is_converted(), db.base_currency());
+
+        let mut rows = vec![];
+        if by_group {
+            for (grp_name, worth) in summary.groups() {
+                rows.push((Some(Field::opt_text(grp_name.as_deref())), worth));
+            }
+        }
+        rows.push((by_group.then(|| Field::text("Total")), summary.total()));
+
+        for (group, worth) in rows {
+            let mut row = vec![date.clone()];
+            row.extend(group);
+            row.extend([
+                Field::Amount(worth.assets(), decimals),
+                Field::Amount(worth.liabilities(), decimals),
+                Field::Amount(worth.net(), decimals),
+                conv.clone(),
+            ]);
+            records.push(row);
+        }
+    }
+
+    records
+}
+
+/// Columns: `period`, `income`, `expense`, `net`, `savings_rate`, `converted`.
+///
+/// `expense` is negative, and `savings_rate` is a percentage of the income, which is empty if there was no income.
+pub fn cashflow(flows: &[Cashflow], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "period",
+        "income",
+        "expense",
+        "net",
+        "savings_rate",
+        "converted",
+    ]);
+
+    for flow in flows {
+        records.push(vec![
+            Field::text(flow.label()),
+            Field::Amount(flow.income(), decimals),
+            Field::Amount(flow.expense(), decimals),
+            Field::Amount(flow.net(), decimals),
+            flow.savings_rate()
+                .map_or(Field::Null, |rate| Field::Number(rate, 1)),
+            converted(flow.is_converted(), db.base_currency()),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `category`, a column named after each period, `total`, `converted`.
+///
+/// The name of a subcategory includes its parent category, like `Food:Groceries`.
+pub fn trend(trends: &[Trend], labels: &[String], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut columns = vec!["category".to_string()];
+    columns.extend(labels.iter().cloned());
+    columns.extend(["total".to_string(), "converted".to_string()]);
+    let mut records = Records::with_columns(columns);
+
+    for trend in trends {
+        let mut row = vec![Field::Text(trend.full_name())];
+        row.extend(trend.sums().iter().map(|sum| Field::Amount(*sum, decimals)));
+        row.extend([
+            Field::Amount(trend.total(), decimals),
+            converted(trend.is_converted(), db.base_currency()),
+        ]);
+        records.push(row);
+    }
+
+    records
+}
+
+/// Columns: `category`, `income`, `progress`, `allotment`, `carried`, `available`, `fraction`, `converted`.
+///
+/// `fraction` is the progress as a fraction of the available amount, which is empty if there is no allotment or nothing is available.
+pub fn budget(
+    summaries: &[BudgetSummary],
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&[
+        "category",
+        "income",
+        "progress",
+        "allotment",
+        "carried",
+        "available",
+        "fraction",
+        "converted",
+    ]);
+
+    for summary in summaries {
+        let alert = summary.is_over_budget() || summary.is_under_target();
+        records.push(vec![
+            Field::text(summary.name()),
+            Field::flag(summary.is_income(), "income"),
+            Field::Amount(summary.progress(), decimals).alert_if(alert),
+            summary
+                .allotment()
+                .map_or(Field::Null, |val| Field::Amount(val, decimals)),
+            Field::Amount(summary.carried(), decimals),
+            summary
+                .available()
+                .map_or(Field::Null, |val| Field::Amount(val, decimals)),
+            summary
+                .progress_frac()
+                .map_or(Field::Null, |frac| Field::Number(frac.into(), 2)),
+            converted(summary.is_converted(), currency),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `category`, `measure`, a column for each month of the year like `2021-01`, `converted`.
+///
+/// Each category has four rows, where `measure` is `budget`, `actual`, `variance`, and `ytd_variance`.
+pub fn budget_matrix(
+    rows: &[BudgetMatrixRow],
+    year: i32,
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut columns = vec!["category".to_string(), "measure".to_string()];
+    columns.extend((1..=12).map(|m| format!("{year}-{m:02}")));
+    columns.push("converted".to_string());
+    let mut records = Records::with_columns(columns);
+
+    for row in rows {
+        let measures: [(&str, Vec<Field>); 4] = [
+            (
+                "budget",
+                row.months()
+                    .iter()
+                    .map(|m| Field::Amount(m.allotment(), decimals))
+                    .collect(),
+            ),
+            (
+                "actual",
+                row.months()
+                    .iter()
+                    .map(|m| Field::Amount(m.actual(), decimals))
+                    .collect(),
+            ),
+            (
+                "variance",
+                row.months()
+                    .iter()
+                    .map(|m| Field::Amount(m.variance(), decimals).alert_if(m.is_over_budget()))
+                    .collect(),
+            ),
+            (
+                "ytd_variance",
+                row.months()
+                    .iter()
+                    .map(|m| {
+                        Field::Amount(m.ytd_variance(), decimals)
+                            .alert_if(m.ytd_variance().is_negative())
+                    })
+                    .collect(),
+            ),
+        ];
+
+        for (measure, cells) in measures {
+            let mut fields = vec![Field::text(row.name()), Field::text(measure)];
+            fields.extend(cells);
+            fields.push(converted(row.is_converted(), currency));
+            records.push(fields);
+        }
+    }
+
+    records
+}
+
+/// Columns: `category`, `income`, `allotment`, `spent`, `scheduled`, `projected`, `status`, `converted`.
+///
+/// `status` is `over budget`, `projected over budget`, or `projected short of target`, and empty otherwise.
+pub fn budget_projection(
+    projections: &[BudgetProjection],
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&[
+        "category",
+        "income",
+        "allotment",
+        "spent",
+        "scheduled",
+        "projected",
+        "status",
+        "converted",
+    ]);
+
+    for proj in projections {
+        let status = if proj.is_over() {
+            Field::text("over budget")
+        } else if proj.is_projected_over() {
+            Field::text("projected over budget")
+        } else if proj.is_projected_short() {
+            Field::text("projected short of target")
+        } else {
+            Field::Null
+        };
+
+        records.push(vec![
+            Field::text(proj.name()),
+            Field::flag(proj.is_income(), "income"),
+            proj.allotment()
+                .map_or(Field::Null, |val| Field::Amount(val, decimals)),
+            Field::Amount(proj.spent(), decimals),
+            Field::Amount(proj.scheduled(), decimals),
+            Field::Amount(proj.projected(), decimals)
+                .alert_if(proj.is_projected_over() || proj.is_projected_short()),
+            status,
+            converted(proj.is_converted(), currency),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `category`, `subcategory`, `sum`, `converted`.
+pub fn review(
+    review: &[(String, Option<String>, Money, bool)],
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&["category", "subcategory", "sum", "converted"]);
+
+    for (cat, subcat, sum, is_converted) in review {
+        records.push(vec![
+            Field::text(cat),
+            Field::opt_text(subcat.as_deref()),
+            Field::Amount(*sum, decimals),
+            converted(*is_converted, currency),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `category`, `depth`, `sum`, `converted`.
+///
+/// `depth` is how many parent categories a category has, so `0` for top-level categories; each category's sum includes its subcategories'.
+/// The last row is the total across the parent categories, where `category` is `Total` and `depth` is empty.
+/// Subcategories are only indented in the table format.
+pub fn review_rollup(
+    rollups: &[CategoryRollup],
+    format: OutputFormat,
+    currency: Option<&Currency>,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&["category", "depth", "sum", "converted"]);
+    let mut total = Money::ZERO;
+    let mut any_converted = false;
+
+    for rollup in rollups {
+        if rollup.depth() == 0 {
+            total += rollup.sum();
+            any_converted |= rollup.is_converted();
+        }
+        let name = if format == OutputFormat::Table {
+            format!("{}{}", "  ".repeat(rollup.depth()), rollup.name())
+        } else {
+            rollup.name().to_string()
+        };
+
+        records.push(vec![
+            Field::Text(name),
+            Field::Int(rollup.depth() as i64),
+            Field::Amount(rollup.sum(), decimals),
+            converted(rollup.is_converted(), currency),
+        ]);
+    }
+
+    records.push(vec![
+        Field::text("Total"),
+        Field::Null,
+        Field::Amount(total, decimals),
+        converted(any_converted, currency),
+    ]);
+
+    records
+}
+
+/// Columns: `date`, `account`, `payee`, `category`, `memo`, `amount`, `balance`.
+///
+/// `balance` is the balance of the account after the scheduled transaction.
+pub fn upcoming(upcoming: &[UpcomingTransaction], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&[
+        "date", "account", "payee", "category", "memo", "amount", "balance",
+    ]);
+
+    for up in upcoming {
+        let tr = up.transaction();
+        let decimals = account_decimals(db, tr.account(), decimals);
+        records.push(vec![
+            Field::Text(tr.date().to_string()),
+            Field::opt_text(tr.account_name(db).as_deref()),
+            Field::opt_text(tr.payee_name(db).as_deref()),
+            category_names(tr, db),
+            Field::opt_text(tr.memo().as_deref()),
+            Field::Amount(*tr.total(), decimals),
+            Field::Amount(up.balance(), decimals),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `date`, `account`, `payee`, `memo`, `amount`, `category`, `search`.
+///
+/// `category` is what the assignment rule matching on `search` would categorize the transaction as.
+pub fn auto_assign(
+    fixes: &[(Transaction, Assignment)],
+    db: &HomeBankDb,
+    decimals: usize,
+) -> Records {
+    let mut records = Records::new(&[
+        "date", "account", "payee", "memo", "amount", "category", "search",
+    ]);
+
+    for (tr, asg) in fixes {
+        let decimals = account_decimals(db, tr.account(), decimals);
+        records.push(vec![
+            Field::Text(tr.date().to_string()),
+            Field::opt_text(tr.account_name(db).as_deref()),
+            Field::opt_text(tr.payee_name(db).as_deref()),
+            Field::opt_text(tr.memo().as_deref()),
+            Field::Amount(*tr.total(), decimals),
+            category_names(tr, db),
+            Field::text(asg.search()),
+        ]);
+    }
+
+    records
+}
+
+/// Columns: `date`, `account`, `payee`, `memo`, `amount`.
+pub fn posted(posted: &[Transaction], db: &HomeBankDb, decimals: usize) -> Records {
+    let mut records = Records::new(&["date", "account", "payee", "memo", "amount"]);
+
+    for tr in posted {
+        let decimals = account_decimals(db, tr.account(), decimals);
+        records.push(vec![
+            Field::Text(tr.date().to_string()),
+            Field::opt_text(tr.account_name(db).as_deref()),
+            Field::opt_text(tr.payee_name(db).as_deref()),
+            Field::opt_text(tr.memo().as_deref()),
+            Field::Amount(*tr.total(), decimals),
+        ]);
+    }
+
+    records
+}
@@ -0,0 +1,38 @@
+//! Options for summing transactions in the HomeBank database.
+
+use clap::Parser;
+use homebank_db::{transaction::GroupBy, QueryTransactions};
+
+/// Options for summing transactions in the HomeBank database.
+#[derive(Debug, Parser)]
+pub struct SumOpts {
+    /// The transactions to sum.
+    #[clap(flatten)]
+    query: QueryTransactions,
+
+    /// Convert the sum into the currency with this ISO code, instead of the base currency
+    #[clap(long = "currency", value_name = "ISO")]
+    currency: Option<String>,
+
+    /// Sum each group of transactions separately, with their count, average, smallest, and largest amounts.
+    /// Group by 'week', 'month', 'year', 'payee', 'category', 'account', 'tag', 'paymode', or 'status'.
+    #[clap(long = "by", value_name = "group")]
+    by: Option<GroupBy>,
+}
+
+impl SumOpts {
+    /// Retrieve the query for the transactions to sum
+    pub fn query(&self) -> &QueryTransactions {
+        &self.query
+    }
+
+    /// Retrieve the ISO code of the currency to convert into, if not the base currency
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+
+    /// Retrieve what to group the transactions by, if they are summed in groups
+    pub fn by(&self) -> Option<GroupBy> {
+        self.by
+    }
+}
@@ -37,10 +37,10 @@ impl TryFrom<&CliOpts> for Config {
     fn try_from(opts: &CliOpts) -> Result<Self, Self::Error> {
         // check that the config file exists
         if !opts.path.exists() {
-            return Err(ConfigError::DoesNotExist(opts.path().to_path_buf()));
+            Err(ConfigError::DoesNotExist(opts.path().to_path_buf()))
         } else if !opts.path.is_file() {
             // check that the config is a file
-            return Err(ConfigError::NotAFile(opts.path().to_path_buf()));
+            Err(ConfigError::NotAFile(opts.path().to_path_buf()))
         } else {
             // read the file and parse its contents
             let file_contents = match file_to_string(&opts.path) {
@@ -102,6 +102,7 @@ pub fn default_cfg_file() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::OutputFormat;
     use dirs_next::home_dir;
 
     #[test]
@@ -204,6 +205,9 @@ mod tests {
     fn try_from_directory_config() {
         let cli_opts = CliOpts {
             path: PathBuf::from("./src"),
+            strict: false,
+            format: OutputFormat::Table,
+            columns: None,
             subcmd: None,
         };
         let expected = Config::new(Path::new("path"));
@@ -216,6 +220,9 @@ mod tests {
     fn try_from_nonexistent_config() {
         let cli_opts = CliOpts {
             path: PathBuf::from("path/to/nonexistent/directory/file.toml"),
+            strict: false,
+            format: OutputFormat::Table,
+            columns: None,
             subcmd: None,
         };
         let expected = Config::new(Path::new(""));
@@ -280,6 +287,6 @@ mod tests {
         let input = "path = '/etc/passwd'";
         let expected = Config::new(Path::new("/etc/passwd"));
 
-        check_try_from_toml(&input, expected);
+        check_try_from_toml(input, expected);
     }
 }
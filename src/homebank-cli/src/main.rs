@@ -5,9 +5,12 @@
 
 use anyhow::Context;
 use clap::Parser;
-use cli::{budget::budget_pbar, CliOpts, SubCommand};
+use cli::{budget_pbar, schema, skipped_summary, target_currency, CliOpts, OutputFormat, SubCommand};
 use config::Config;
-use homebank_db::{transaction::sum_transactions, HomeBankDb, Query, QueryType};
+use homebank_db::{
+    category::TODAY, db::HomeBankDbError, transaction::{group_transactions, sum_transactions_in}, HomeBankDb, Query,
+    QueryType,
+};
 
 pub mod cli;
 pub mod config;
@@ -17,82 +20,160 @@ fn main() -> Result<(), anyhow::Error> {
     let cli_opts = CliOpts::parse();
 
     let cfg = Config::try_from(&cli_opts)?;
-    let db = match HomeBankDb::try_from(cfg.path()) {
-        Ok(db) => db,
-        Err(e) => return Err(e).with_context(|| "Error parsing HomeBank file."),
-    };
-
-    match &cli_opts.subcommand() {
-        Some(SubCommand::Query(q_opts)) => match q_opts.qtype() {
-            QueryType::Transactions(query) => {
-                let filt_transactions = query.exec(&db);
-
-                println!("{:#?}", filt_transactions);
-            }
-            QueryType::Payees(query) => {
-                let filt_payees = query.exec(&db);
-
-                println!("{:#?}", filt_payees);
-            }
-            QueryType::Currencies(query) => {
-                let filt_currencies = query.exec(&db);
-
-                println!("{:#?}", filt_currencies);
+    let (mut db, num_skipped) = if cli_opts.strict() {
+        match HomeBankDb::load_strict(cfg.path()) {
+            Ok(db) => (db, 0),
+            Err(HomeBankDbError::InvalidElements(path, diagnostics)) => {
+                for d in &diagnostics {
+                    eprintln!("{d}");
+                }
+                return Err(HomeBankDbError::InvalidElements(path, diagnostics))
+                    .with_context(|| "Error parsing HomeBank file.");
             }
-            QueryType::Categories(query) => {
-                let filt_categories = query.exec(&db);
-
-                for cat in filt_categories {
-                    println!("{}", cat.full_name(&db));
+            Err(e) => return Err(e).with_context(|| "Error parsing HomeBank file."),
+        }
+    } else {
+        match HomeBankDb::load_lenient(cfg.path()) {
+            Ok((db, diagnostics)) => {
+                // warn about anything left out, since it won't be included in any sums
+                for line in skipped_summary(&diagnostics) {
+                    eprintln!("Warning: {line}. Use `--strict` for details.");
                 }
+                (db, diagnostics.len())
             }
-            QueryType::Accounts(query) => {
-                let filt_accounts = query.exec(&db);
+            Err(e) => return Err(e).with_context(|| "Error parsing HomeBank file."),
+        }
+    };
 
-                println!("{:#?}", filt_accounts);
-            }
-            QueryType::Groups(query) => {
-                let filt_groups = query.exec(&db);
+    // amounts are displayed with as many decimals as the base currency uses
+    let decimals = db.base_currency().map_or(2, |curr| curr.decimal_len());
+    let format = cli_opts.format();
 
-                println!("{:#?}", filt_groups);
-            }
+    let records = match &cli_opts.subcommand() {
+        Some(SubCommand::Query(q_opts)) => match q_opts.qtype() {
+            QueryType::Transactions(query) => schema::transactions(&query.exec(&db), &db, decimals),
+            QueryType::Payees(query) => schema::payees(&query.exec(&db), &db),
+            QueryType::Currencies(query) => schema::currencies(&query.exec(&db)),
+            QueryType::Categories(query) => schema::categories(&query.exec(&db), &db),
+            QueryType::Accounts(query) => schema::accounts(&query.exec(&db), &db, decimals),
+            QueryType::Groups(query) => schema::groups(&query.exec(&db)),
+            QueryType::Assignments(query) => schema::assignments(&query.exec(&db), &db),
+            QueryType::Tags(query) => schema::tags(&query.exec(&db), decimals),
+            QueryType::Templates(query) => schema::templates(&query.exec(&db), &db, decimals),
         },
-        // QueryType::Templates(query) => {
-        //     let filt_templates = query.exec(&db);
-
-        //     println!("{:#?}", filt_templates);
-        // }
-        Some(SubCommand::Sum(query)) => {
-            let filt_transactions = query.exec(&db);
-            let sum = sum_transactions(&filt_transactions);
-            println!("{sum:.2}");
+        Some(SubCommand::Sum(opts)) => {
+            let currency = target_currency(&db, opts.currency())?;
+            let currency_key = currency.map_or(db.properties().currency(), |curr| curr.key());
+            let decimals = currency.map_or(decimals, |curr| curr.decimal_len());
+
+            let filt_transactions = opts.query().exec(&db);
+            match opts.by() {
+                Some(by) => {
+                    let groups = group_transactions(&filt_transactions, by, &db, currency_key);
+                    schema::sum_groups(&groups, currency, decimals)
+                }
+                None => {
+                    let (sum, converted) =
+                        sum_transactions_in(&filt_transactions, &db, currency_key);
+                    schema::sum(sum, converted, currency, decimals)
+                }
+            }
+        }
+        Some(SubCommand::Balance(query)) => {
+            // subtotals are converted into the base currency
+            target_currency(&db, None)?;
+            schema::balances(&query.exec(&db), &db, decimals)
+        }
+        Some(SubCommand::Register(query)) => schema::register(&query.exec(&db), &db, decimals),
+        Some(SubCommand::Networth(opts)) => {
+            target_currency(&db, None)?;
+            schema::networth(&opts.query().exec(&db), opts.by_group(), &db, decimals)
+        }
+        Some(SubCommand::Cashflow(query)) => {
+            target_currency(&db, None)?;
+            schema::cashflow(&query.exec(&db), &db, decimals)
+        }
+        Some(SubCommand::Trend(query)) => {
+            target_currency(&db, None)?;
+            schema::trend(&query.exec(&db), &query.labels(), &db, decimals)
         }
         Some(SubCommand::Budget(query)) => {
-            let filt_budget = query.exec(&db);
+            let currency = target_currency(&db, query.currency())?;
+            let decimals = currency.map_or(decimals, |curr| curr.decimal_len());
+
+            let records = if query.matrix() {
+                schema::budget_matrix(&query.exec_matrix(&db), query.year(), currency, decimals)
+            } else if query.projection() {
+                schema::budget_projection(&query.exec_projection(&db), currency, decimals)
+            } else if format == OutputFormat::Table {
+                // budgets are shown as progress bars in the terminal
+                let summaries = query.exec(&db);
+                if summaries.is_empty() {
+                    eprintln!("No budget(s) set for the matching categories.");
+                }
+                for summary in summaries {
+                    let pbar = budget_pbar(summary);
+                    pbar.abandon();
+                }
 
-            if filt_budget.is_empty() {
+                return Ok(());
+            } else {
+                schema::budget(&query.exec(&db), currency, decimals)
+            };
+
+            if records.rows().is_empty() {
                 eprintln!("No budget(s) set for the matching categories.");
             }
 
-            for summary in filt_budget {
-                let pbar = budget_pbar(summary);
-                pbar.abandon();
-            }
+            records
         }
         Some(SubCommand::Review(query)) => {
-            let review = query.exec(&db);
-
-            // print the values in a tab-separated format
-            for (cat, subcat, sum) in review {
-                if let Some(subcat_name) = subcat {
-                    println!("{cat}\t{subcat_name}\t{sum:.2}");
-                } else {
-                    println!("{cat}\t\t{sum:.2}");
-                }
+            let currency = target_currency(&db, query.currency())?;
+            let decimals = currency.map_or(decimals, |curr| curr.decimal_len());
+
+            if query.rollup() {
+                schema::review_rollup(&query.exec_rollup(&db), format, currency, decimals)
+            } else {
+                schema::review(&query.exec(&db), currency, decimals)
             }
         }
-        None => {}
-    }
+        Some(SubCommand::Upcoming(query)) => schema::upcoming(&query.exec(&db), &db, decimals),
+        Some(SubCommand::Assign(query)) => schema::auto_assign(&query.exec(&db), &db, decimals),
+        Some(SubCommand::PostScheduled(opts)) => {
+            // writing the file back out would drop anything that was skipped
+            if !opts.dry_run() && num_skipped > 0 {
+                anyhow::bail!(
+                    "Not posting scheduled transactions, since {num_skipped} entries in the HomeBank file could not be parsed."
+                );
+            }
+            // nor write out a file that wouldn't keep everything that was in it
+            if !opts.dry_run() {
+                db.check_round_trip(cfg.path()).with_context(|| {
+                    "Not posting scheduled transactions, since the HomeBank file could not be written back out as it is."
+                })?;
+            }
+
+            let posted = if opts.dry_run() {
+                db.scheduled_due(&TODAY)
+            } else {
+                db.post_scheduled(&TODAY)
+            };
+
+            if !opts.dry_run() && !posted.is_empty() {
+                db.write_to_path(cfg.path())
+                    .with_context(|| "Error writing HomeBank file.")?;
+            }
+
+            schema::posted(&posted, &db, decimals)
+        }
+        None => return Ok(()),
+    };
+
+    let records = match cli_opts.columns() {
+        Some(columns) => records.select(columns)?,
+        None => records,
+    };
+    records.print(format)?;
 
     Ok(())
 }
@@ -29,15 +29,15 @@ pub enum AccountError {
     #[error("Invalid account name.")]
     InvalidName,
 
-    /// The initial amount provided does not properly parse to an `f32`.
+    /// The initial amount provided does not properly parse to an amount of [`Money`][crate::money::money_struct::Money].
     #[error("Invalid initial amount for account.")]
     InvalidInitialAmount,
 
-    /// The minimum amount provided does not properly parse to an `f32`.
+    /// The minimum amount provided does not properly parse to an amount of [`Money`][crate::money::money_struct::Money].
     #[error("Invalid account minimum amount.")]
     InvalidMinimumAmount,
 
-    /// The maxiumim amount provided does not properly parse to an `f32`.
+    /// The maxiumim amount provided does not properly parse to an amount of [`Money`][crate::money::money_struct::Money].
     #[error("Invalid account maximum amount.")]
     InvalidMaximumAmount,
 
@@ -49,6 +49,10 @@ pub enum AccountError {
     #[error("Invalid account group index.")]
     InvalidGroup,
 
+    /// When a cheque number is not a whole number.
+    #[error("Invalid account cheque number.")]
+    InvalidChequeNumber,
+
     /// When the recondiled date provided cannot be properly parsed into a `NaiveDate`.
     #[error("Invalid account date.")]
     InvalidReconcileDate,
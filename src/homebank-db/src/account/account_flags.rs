@@ -0,0 +1,45 @@
+//! Flags set on an [`Account`][crate::account::account_struct::Account].
+
+use crate::flags::flag_set;
+
+flag_set! {
+    /// Flags set on an [`Account`][crate::account::account_struct::Account].
+    pub struct AccountFlags {
+        /// The account has been closed (`AF_CLOSED`).
+        const CLOSED = 1 << 1;
+
+        /// The account is left out of the accounts summary (`AF_NOSUMMARY`).
+        const NO_SUMMARY = 1 << 4;
+
+        /// The account is left out of budgets (`AF_NOBUDGET`).
+        const NO_BUDGET = 1 << 5;
+
+        /// The account is left out of reports (`AF_NOREPORT`).
+        const NO_REPORT = 1 << 6;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_bits_are_kept() {
+        let flags = AccountFlags::from(0b1100_0010);
+
+        assert!(flags.contains(AccountFlags::CLOSED));
+        assert!(flags.contains(AccountFlags::NO_REPORT));
+        assert!(!flags.contains(AccountFlags::NO_BUDGET));
+        assert_eq!("194", flags.to_string());
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut flags = AccountFlags::empty();
+        flags.insert(AccountFlags::CLOSED | AccountFlags::NO_SUMMARY);
+        assert_eq!(18, flags.bits());
+
+        flags.remove(AccountFlags::CLOSED);
+        assert_eq!(AccountFlags::NO_SUMMARY, flags);
+    }
+}
@@ -19,6 +19,14 @@ pub struct QueryAccounts {
     /// Include accounts whose institutions match the regular expression.
     #[clap(short = 'i', long = "institution", value_name = "regex")]
     institution: Option<Regex>,
+
+    /// Only include accounts that have been closed.
+    #[clap(short = 'c', long = "closed", conflicts_with = "open")]
+    closed: bool,
+
+    /// Only include accounts that are still open.
+    #[clap(short = 'o', long = "open")]
+    open: bool,
 }
 
 impl QueryAccounts {
@@ -36,6 +44,16 @@ impl QueryAccounts {
     fn institution(&self) -> &Option<Regex> {
         &self.institution
     }
+
+    /// Retrieve whether only closed [`Account`s][crate::account::account_struct::Account] are included.
+    fn closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Retrieve whether only open [`Account`s][crate::account::account_struct::Account] are included.
+    fn open(&self) -> bool {
+        self.open
+    }
 }
 
 impl Query for QueryAccounts {
@@ -67,9 +85,66 @@ impl Query for QueryAccounts {
                 Some(re) => re.is_match(acct.institution()),
                 None => true,
             })
+            // filter closed or open accounts
+            .filter(|&acct| match (self.closed(), self.open()) {
+                (true, _) => acct.is_closed(),
+                (_, true) => !acct.is_closed(),
+                _ => true,
+            })
             .cloned()
             .collect();
 
         filt_accounts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_exec(query: QueryAccounts, expected: Vec<&str>) {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" flags="0" pos="1" type="1" curr="1" name="Chequing"/>
+<account key="2" flags="2" pos="2" type="1" curr="1" name="Old savings"/>
+<account key="3" flags="64" pos="3" type="1" curr="1" name="Wallet"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let mut observed: Vec<String> = query
+            .exec(&db)
+            .iter()
+            .map(|acct| acct.name().to_string())
+            .collect();
+        observed.sort();
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn closed_accounts() {
+        let query = QueryAccounts {
+            acct_type: None,
+            group: None,
+            institution: None,
+            closed: true,
+            open: false,
+        };
+
+        check_exec(query, vec!["Old savings"]);
+    }
+
+    #[test]
+    fn open_accounts() {
+        let query = QueryAccounts {
+            acct_type: None,
+            group: None,
+            institution: None,
+            closed: false,
+            open: true,
+        };
+
+        check_exec(query, vec!["Chequing", "Wallet"]);
+    }
+}
@@ -1,9 +1,13 @@
 //! Chequing accounts, credits cards, and details for all kinds of accounts.
 
-use super::{AccountError, AccountType};
-use crate::transaction::julian_date_from_u32;
+use super::{AccountError, AccountFlags, AccountType};
+use crate::{
+    db::db_writer::xml_attribute,
+    transaction::{julian_date_from_u32, julian_date_to_u32},
+    HomeBankDb, Money, Transaction, TransactionStatus,
+};
 use chrono::NaiveDate;
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 use xml::attribute::OwnedAttribute;
 
 /// Chequing accounts, credits cards, and details for all kinds of accounts.
@@ -13,7 +17,7 @@ pub struct Account {
     key: usize,
 
     /// Flags on this account.
-    flags: usize,
+    flags: AccountFlags,
 
     /// Display position.
     pos: usize,
@@ -27,17 +31,29 @@ pub struct Account {
     /// Account name.
     name: String,
 
+    /// Account number at the institution.
+    number: String,
+
     /// Institution where the account is managed.
     bank_name: String,
 
+    /// Website of the institution.
+    website: String,
+
     /// Initial starting amount.
-    initial_amount: f32,
+    initial_amount: Money,
 
     /// Overdraft amount.
-    minimum_amount: f32,
+    minimum_amount: Money,
 
     /// Maximum total amount.
-    maximum_amount: f32,
+    maximum_amount: Money,
+
+    /// Next cheque number for the first cheque book.
+    cheque1: usize,
+
+    /// Next cheque number for the second cheque book.
+    cheque2: usize,
 
     /// User-provided notes.
     notes: String,
@@ -45,26 +61,30 @@ pub struct Account {
     /// Index of the group this account belongs to, if any.
     group_idx: Option<usize>,
 
-    /// Last reconciled date for [`Transaction`s][crate::transaction::transaction_struct::Transaction] associated with this account.
-    reconciled_date: NaiveDate,
+    /// Last reconciled date for [`Transaction`s][crate::transaction::transaction_struct::Transaction] associated with this account, if it has ever been reconciled.
+    reconciled_date: Option<NaiveDate>,
 }
 
 impl Account {
     pub fn empty() -> Self {
         Self {
             key: 0,
-            flags: 0,
+            flags: AccountFlags::empty(),
             pos: 0,
             atype: AccountType::None,
             currency_idx: 0,
             name: "".to_string(),
+            number: "".to_string(),
             bank_name: "".to_string(),
-            initial_amount: 0.0,
-            minimum_amount: 0.0,
-            maximum_amount: 0.0,
+            website: "".to_string(),
+            initial_amount: Money::ZERO,
+            minimum_amount: Money::ZERO,
+            maximum_amount: Money::ZERO,
+            cheque1: 0,
+            cheque2: 0,
             notes: "".to_string(),
-            group_idx: Some(0),
-            reconciled_date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            group_idx: None,
+            reconciled_date: None,
         }
     }
 
@@ -78,6 +98,11 @@ impl Account {
         &self.name
     }
 
+    /// Retrieve the account's display position
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
     /// Retrieve the account type
     pub fn atype(&self) -> &AccountType {
         &self.atype
@@ -92,6 +117,144 @@ impl Account {
     pub fn institution(&self) -> &str {
         &self.bank_name
     }
+
+    /// Retrieve the key of the [`Currency`][crate::currency::currency_struct::Currency] used in this account
+    pub fn currency(&self) -> usize {
+        self.currency_idx
+    }
+
+    /// Retrieve the account's initial starting amount
+    pub fn initial_amount(&self) -> Money {
+        self.initial_amount
+    }
+
+    /// Retrieve the account's flags
+    pub fn flags(&self) -> AccountFlags {
+        self.flags
+    }
+
+    /// Check if the account has been closed
+    pub fn is_closed(&self) -> bool {
+        self.flags.contains(AccountFlags::CLOSED)
+    }
+
+    /// Check if the account is left out of the accounts summary
+    pub fn is_excluded_from_summary(&self) -> bool {
+        self.flags.contains(AccountFlags::NO_SUMMARY)
+    }
+
+    /// Check if the account is left out of budgets
+    pub fn is_excluded_from_budget(&self) -> bool {
+        self.flags.contains(AccountFlags::NO_BUDGET)
+    }
+
+    /// Check if the account is left out of reports
+    pub fn is_excluded_from_reports(&self) -> bool {
+        self.flags.contains(AccountFlags::NO_REPORT)
+    }
+
+    /// Calculate the account's balance at the end of a given date.
+    ///
+    /// Void transactions and reminders aren't included, like in HomeBank.
+    pub fn balance_at(&self, db: &HomeBankDb, date: &NaiveDate) -> Money {
+        self.balance_of(db, |tr| tr.date() <= date)
+    }
+
+    /// Calculate the account's balance at the end of a given date, only including transactions with at least the given status.
+    ///
+    /// Reconciled transactions count as cleared, so a `Cleared` status includes both.
+    pub fn balance_with_status_at(
+        &self,
+        db: &HomeBankDb,
+        date: &NaiveDate,
+        status: &TransactionStatus,
+    ) -> Money {
+        self.balance_of(db, |tr| {
+            tr.date() <= date && status_counts_towards(tr.status(), status)
+        })
+    }
+
+    /// Calculate the account's balance including every transaction, even those in the future.
+    pub fn future_balance(&self, db: &HomeBankDb) -> Money {
+        self.balance_of(db, |_| true)
+    }
+
+    /// Calculate the account's balance from the transactions that count towards it and are kept by `keep`.
+    ///
+    /// This reads the transactions in place instead of going through [`transactions`][Account::transactions],
+    /// since balances are calculated for every account and every period in some reports.
+    fn balance_of<F: Fn(&Transaction) -> bool>(&self, db: &HomeBankDb, keep: F) -> Money {
+        let own_transfers = self.own_transfer_keys(db);
+
+        self.initial_amount
+            + db.transactions()
+                .iter()
+                .filter(|tr| tr.counts_towards_balance() && keep(tr))
+                .filter_map(|tr| {
+                    if tr.account() == self.key {
+                        Some(*tr.total())
+                    } else if self.is_missing_counterpart(tr, &own_transfers) {
+                        // the counterpart has the same date and status, with the opposite amount
+                        Some(-*tr.total())
+                    } else {
+                        None
+                    }
+                })
+                .sum::<Money>()
+    }
+
+    /// Retrieve every [`Transaction`][crate::transaction::transaction_struct::Transaction] in the account, in date order.
+    ///
+    /// Transfers are usually recorded in both accounts.
+    /// If only the source side of a transfer is in the database, its [`counterpart`][crate::transaction::transaction_struct::Transaction::transfer_counterpart] is included here as well.
+    pub fn transactions(&self, db: &HomeBankDb) -> Vec<Transaction> {
+        let own_transfers = self.own_transfer_keys(db);
+
+        let mut v: Vec<Transaction> = db
+            .transactions()
+            .iter()
+            .filter_map(|tr| {
+                if tr.account() == self.key {
+                    Some(tr.clone())
+                } else if self.is_missing_counterpart(tr, &own_transfers) {
+                    tr.transfer_counterpart()
+                } else {
+                    None
+                }
+            })
+            .collect();
+        v.sort_by_key(|tr| *tr.date());
+
+        v
+    }
+
+    /// Collect the transfer keys of the transfers recorded in this account.
+    fn own_transfer_keys(&self, db: &HomeBankDb) -> HashSet<usize> {
+        db.transactions()
+            .iter()
+            .filter(|tr| tr.account() == self.key)
+            .filter_map(|tr| tr.transfer_key().copied())
+            .collect()
+    }
+
+    /// Check if a transaction is a transfer into this account whose other side isn't recorded here.
+    fn is_missing_counterpart(&self, tr: &Transaction, own_transfers: &HashSet<usize>) -> bool {
+        tr.transfer_destination() == Some(&self.key)
+            && !tr
+                .transfer_key()
+                .map_or(false, |xfer| own_transfers.contains(xfer))
+    }
+}
+
+/// Check if a transaction's status is at least `status`, where reconciled transactions are also cleared.
+fn status_counts_towards(tr_status: &TransactionStatus, status: &TransactionStatus) -> bool {
+    match status {
+        TransactionStatus::Cleared => matches!(
+            tr_status,
+            TransactionStatus::Cleared | TransactionStatus::Reconciled
+        ),
+        _ => tr_status == status,
+    }
 }
 
 impl Default for Account {
@@ -111,9 +274,27 @@ impl TryFrom<Vec<OwnedAttribute>> for Account {
                 "name" => {
                     acct.name = i.value.to_string();
                 }
+                "number" => {
+                    acct.number = i.value.to_string();
+                }
                 "bankname" => {
                     acct.bank_name = i.value.to_string();
                 }
+                "website" => {
+                    acct.website = i.value.to_string();
+                }
+                "cheque1" => {
+                    acct.cheque1 = match usize::from_str(&i.value) {
+                        Ok(n) => n,
+                        Err(_) => return Err(AccountError::InvalidChequeNumber),
+                    }
+                }
+                "cheque2" => {
+                    acct.cheque2 = match usize::from_str(&i.value) {
+                        Ok(n) => n,
+                        Err(_) => return Err(AccountError::InvalidChequeNumber),
+                    }
+                }
                 "notes" => {
                     acct.notes = i.value.to_string();
                 }
@@ -125,7 +306,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Account {
                 }
                 "flags" => {
                     acct.flags = match usize::from_str(&i.value) {
-                        Ok(idx) => idx,
+                        Ok(f) => AccountFlags::from(f),
                         Err(_) => return Err(AccountError::InvalidFlags),
                     }
                 }
@@ -137,10 +318,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Account {
                 }
                 "type" => {
                     acct.atype = match usize::from_str(&i.value) {
-                        Ok(idx) => match AccountType::try_from(idx) {
-                            Ok(atype) => atype,
-                            Err(e) => return Err(e),
-                        },
+                        Ok(idx) => AccountType::try_from(idx)?,
                         Err(_) => return Err(AccountError::InvalidType),
                     }
                 }
@@ -151,19 +329,19 @@ impl TryFrom<Vec<OwnedAttribute>> for Account {
                     }
                 }
                 "initial" => {
-                    acct.initial_amount = match f32::from_str(&i.value) {
+                    acct.initial_amount = match Money::from_str(&i.value) {
                         Ok(a) => a,
                         Err(_) => return Err(AccountError::InvalidInitialAmount),
                     }
                 }
                 "minimum" => {
-                    acct.minimum_amount = match f32::from_str(&i.value) {
+                    acct.minimum_amount = match Money::from_str(&i.value) {
                         Ok(a) => a,
                         Err(_) => return Err(AccountError::InvalidMinimumAmount),
                     }
                 }
                 "maximum" => {
-                    acct.maximum_amount = match f32::from_str(&i.value) {
+                    acct.maximum_amount = match Money::from_str(&i.value) {
                         Ok(a) => a,
                         Err(_) => return Err(AccountError::InvalidMaximumAmount),
                     }
@@ -176,7 +354,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Account {
                 }
                 "rdate" => {
                     acct.reconciled_date = match u32::from_str(&i.value) {
-                        Ok(d) => julian_date_from_u32(d),
+                        Ok(d) => Some(julian_date_from_u32(d)),
                         Err(_) => return Err(AccountError::InvalidReconcileDate),
                     }
                 }
@@ -187,11 +365,108 @@ impl TryFrom<Vec<OwnedAttribute>> for Account {
     }
 }
 
+impl From<&Account> for Vec<OwnedAttribute> {
+    fn from(acct: &Account) -> Self {
+        let mut v = vec![xml_attribute("key", acct.key)];
+
+        // HomeBank leaves out numbers of 0 and empty text
+        if acct.flags.bits() != 0 {
+            v.push(xml_attribute("flags", acct.flags));
+        }
+        if acct.pos != 0 {
+            v.push(xml_attribute("pos", acct.pos));
+        }
+        if acct.atype != AccountType::None {
+            v.push(xml_attribute("type", usize::from(acct.atype)));
+        }
+        if acct.currency_idx != 0 {
+            v.push(xml_attribute("curr", acct.currency_idx));
+        }
+        if !acct.name.is_empty() {
+            v.push(xml_attribute("name", &acct.name));
+        }
+        if !acct.number.is_empty() {
+            v.push(xml_attribute("number", &acct.number));
+        }
+        if !acct.bank_name.is_empty() {
+            v.push(xml_attribute("bankname", &acct.bank_name));
+        }
+        v.push(xml_attribute("initial", acct.initial_amount));
+        v.push(xml_attribute("minimum", acct.minimum_amount));
+        v.push(xml_attribute("maximum", acct.maximum_amount));
+        if acct.cheque1 != 0 {
+            v.push(xml_attribute("cheque1", acct.cheque1));
+        }
+        if acct.cheque2 != 0 {
+            v.push(xml_attribute("cheque2", acct.cheque2));
+        }
+        if !acct.notes.is_empty() {
+            v.push(xml_attribute("notes", &acct.notes));
+        }
+        if let Some(grp) = acct.group_idx {
+            v.push(xml_attribute("grp", grp));
+        }
+        if let Some(rdate) = &acct.reconciled_date {
+            v.push(xml_attribute("rdate", julian_date_to_u32(rdate)));
+        }
+        if !acct.website.is_empty() {
+            v.push(xml_attribute("website", &acct.website));
+        }
+
+        v
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         let result = 4;
         assert_eq!(2 + 2, result);
     }
+
+    #[test]
+    fn write_every_parsed_attribute() {
+        let input = vec![
+            xml_attribute("key", 1),
+            xml_attribute("flags", 64),
+            xml_attribute("pos", 1),
+            xml_attribute("type", 1),
+            xml_attribute("curr", 1),
+            xml_attribute("name", "Chequing"),
+            xml_attribute("number", "012-345"),
+            xml_attribute("bankname", "Credit Union"),
+            xml_attribute("initial", 100),
+            xml_attribute("minimum", 0),
+            xml_attribute("maximum", 0),
+            xml_attribute("cheque1", 101),
+            xml_attribute("cheque2", 2001),
+            xml_attribute("notes", "Joint account"),
+            xml_attribute("rdate", 738191),
+            xml_attribute("website", "https://example.com"),
+        ];
+        let acct = Account::try_from(input.clone()).unwrap();
+        let observed: Vec<OwnedAttribute> = (&acct).into();
+
+        // accounts without a group don't get one when written out
+        assert_eq!(None, acct.group());
+        assert_eq!(input, observed);
+    }
+
+    #[test]
+    fn write_leaves_out_unset_attributes() {
+        let input = vec![
+            xml_attribute("key", 2),
+            xml_attribute("name", "Savings"),
+            xml_attribute("initial", 0),
+            xml_attribute("minimum", 0),
+            xml_attribute("maximum", 0),
+        ];
+        let acct = Account::try_from(input.clone()).unwrap();
+        let observed: Vec<OwnedAttribute> = (&acct).into();
+
+        assert_eq!(input, observed);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 //! Chequing, savings, and other types of financial accounts.
 
 use super::AccountError;
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 /// Chequing, savings, and other types of financial accounts.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -16,6 +16,15 @@ pub enum AccountType {
     Savings,
 }
 
+impl AccountType {
+    /// Check if the account holds money that is owed, such as a credit card or a loan.
+    ///
+    /// Every other type of account holds assets.
+    pub fn is_liability(&self) -> bool {
+        matches!(self, AccountType::CreditCard | AccountType::Liability)
+    }
+}
+
 impl TryFrom<usize> for AccountType {
     type Error = AccountError;
 
@@ -34,6 +43,37 @@ impl TryFrom<usize> for AccountType {
     }
 }
 
+impl From<AccountType> for usize {
+    fn from(atype: AccountType) -> Self {
+        match atype {
+            AccountType::None => 0,
+            AccountType::Bank => 1,
+            AccountType::Cash => 2,
+            AccountType::Asset => 3,
+            AccountType::CreditCard => 4,
+            AccountType::Liability => 5,
+            AccountType::Chequing => 6,
+            AccountType::Savings => 7,
+        }
+    }
+}
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AccountType::None => "none",
+            AccountType::Bank => "bank",
+            AccountType::Cash => "cash",
+            AccountType::Asset => "asset",
+            AccountType::CreditCard => "credit_card",
+            AccountType::Liability => "liability",
+            AccountType::Chequing => "chequing",
+            AccountType::Savings => "savings",
+        };
+        write!(f, "{name}")
+    }
+}
+
 impl FromStr for AccountType {
     type Err = AccountError;
 
@@ -0,0 +1,335 @@
+//! Balances of each [`Account`][crate::account::account_struct::Account] in the [`HomeBankDb`], grouped by [`Group`][crate::group::group_struct::Group].
+
+use crate::{
+    category::TODAY, interval::parse_date_until, Account, HomeBankDb, Money, Query,
+    TransactionStatus,
+};
+use chrono::NaiveDate;
+use clap::Parser;
+use std::{collections::BTreeMap, ops::Add};
+
+/// Query the balance of each [`Account`][crate::account::account_struct::Account], like HomeBank's account list.
+#[derive(Debug, Parser)]
+pub struct QueryBalance {
+    /// Calculate the current balance at the end of this date.
+    /// Defaults to today.
+    #[clap(
+        short = 'a',
+        long = "at",
+        parse(try_from_str = parse_date_until),
+        allow_hyphen_values = true,
+        value_name = "date"
+    )]
+    at: Option<NaiveDate>,
+
+    /// Status that transactions need for the cleared balance.
+    /// Reconciled transactions also count as cleared.
+    #[clap(
+        short = 's',
+        long = "status",
+        default_value = "cleared",
+        possible_values = &["cleared", "reconciled"],
+        value_name = "status"
+    )]
+    status: TransactionStatus,
+}
+
+impl QueryBalance {
+    /// Create a new query for account balances
+    pub fn new(at: Option<NaiveDate>, status: TransactionStatus) -> Self {
+        Self { at, status }
+    }
+
+    /// Retrieve the date that the current balance is calculated at
+    fn at(&self) -> NaiveDate {
+        self.at.unwrap_or(*TODAY)
+    }
+
+    /// Retrieve the status that transactions need for the cleared balance
+    fn status(&self) -> &TransactionStatus {
+        &self.status
+    }
+}
+
+/// The cleared, current, and future balances of an [`Account`][crate::account::account_struct::Account] or a [`Group`][crate::group::group_struct::Group] of them.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct Balances {
+    /// Balance of the transactions with the requested status.
+    cleared: Money,
+
+    /// Balance of the transactions up to the requested date.
+    current: Money,
+
+    /// Balance of every transaction, including those in the future.
+    future: Money,
+}
+
+impl Balances {
+    /// Create a new set of balances
+    pub fn new(cleared: Money, current: Money, future: Money) -> Self {
+        Self {
+            cleared,
+            current,
+            future,
+        }
+    }
+
+    /// Retrieve the balance of the transactions with the requested status
+    pub fn cleared(&self) -> Money {
+        self.cleared
+    }
+
+    /// Retrieve the balance of the transactions up to the requested date
+    pub fn current(&self) -> Money {
+        self.current
+    }
+
+    /// Retrieve the balance of every transaction, including those in the future
+    pub fn future(&self) -> Money {
+        self.future
+    }
+}
+
+impl Add for Balances {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.cleared + rhs.cleared,
+            self.current + rhs.current,
+            self.future + rhs.future,
+        )
+    }
+}
+
+/// The balances of a single [`Account`][crate::account::account_struct::Account], in its own currency.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AccountBalance {
+    /// Name of the account.
+    name: String,
+
+    /// Key of the [`Currency`][crate::currency::currency_struct::Currency] the account uses.
+    currency: usize,
+
+    /// Balances of the account.
+    balances: Balances,
+}
+
+impl AccountBalance {
+    /// Create a new account balance
+    pub fn new(name: &str, currency: usize, balances: Balances) -> Self {
+        Self {
+            name: name.to_string(),
+            currency,
+            balances,
+        }
+    }
+
+    /// Retrieve the name of the account
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieve the key of the currency the account uses
+    pub fn currency(&self) -> usize {
+        self.currency
+    }
+
+    /// Retrieve the balances of the account
+    pub fn balances(&self) -> &Balances {
+        &self.balances
+    }
+}
+
+/// The balances of every [`Account`][crate::account::account_struct::Account] in a [`Group`][crate::group::group_struct::Group], and their subtotal.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GroupBalance {
+    /// Name of the group, or `None` for accounts that aren't in a group.
+    name: Option<String>,
+
+    /// Balances of each account in the group.
+    accounts: Vec<AccountBalance>,
+
+    /// Subtotal of the accounts, in the base currency.
+    total: Balances,
+
+    /// Whether any of the subtotal was converted from another currency.
+    converted: bool,
+}
+
+impl GroupBalance {
+    /// Create a new group balance
+    pub fn new(
+        name: Option<String>,
+        accounts: Vec<AccountBalance>,
+        total: Balances,
+        converted: bool,
+    ) -> Self {
+        Self {
+            name,
+            accounts,
+            total,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the group, if the accounts are in one
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Retrieve the balances of each account in the group
+    pub fn accounts(&self) -> &[AccountBalance] {
+        &self.accounts
+    }
+
+    /// Retrieve the subtotal of the accounts, in the base currency
+    pub fn total(&self) -> &Balances {
+        &self.total
+    }
+
+    /// Check if any of the subtotal was converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+impl Query for QueryBalance {
+    type T = GroupBalance;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let at = self.at();
+        let base = db.properties().currency();
+
+        // closed accounts and those left out of the summary aren't listed, like in HomeBank
+        let mut grouped: BTreeMap<(bool, Option<String>), Vec<&Account>> = BTreeMap::new();
+        for acct in db
+            .accounts()
+            .values()
+            .filter(|acct| !acct.is_closed() && !acct.is_excluded_from_summary())
+        {
+            let grp_name = acct
+                .group()
+                .and_then(|idx| db.groups().get(&idx))
+                .map(|grp| grp.name().to_string());
+            // accounts without a group are listed last
+            grouped
+                .entry((grp_name.is_none(), grp_name))
+                .or_default()
+                .push(acct);
+        }
+
+        grouped
+            .into_iter()
+            .map(|((_, grp_name), mut accts)| {
+                accts.sort_by_key(|acct| (acct.position(), acct.name().to_string()));
+
+                let mut total = Balances::default();
+                let mut converted = false;
+                let accounts = accts
+                    .iter()
+                    .map(|acct| {
+                        let balances = Balances::new(
+                            acct.balance_with_status_at(db, &at, self.status()),
+                            acct.balance_at(db, &at),
+                            acct.future_balance(db),
+                        );
+
+                        // subtotals are in the base currency, leaving out accounts that can't be converted into it
+                        let to_base =
+                            |amount: Money| db.convert_for_total(amount, acct.currency(), base);
+                        if let (
+                            Some((cleared, was_converted)),
+                            Some((current, _)),
+                            Some((future, _)),
+                        ) = (
+                            to_base(balances.cleared()),
+                            to_base(balances.current()),
+                            to_base(balances.future()),
+                        ) {
+                            converted |= was_converted;
+                            total = total + Balances::new(cleared, current, future);
+                        }
+
+                        AccountBalance::new(acct.name(), acct.currency(), balances)
+                    })
+                    .collect();
+
+                GroupBalance::new(grp_name, accounts, total, converted)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> HomeBankDb {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<properties title="Test" curr="1"/>
+<cur key="1" flags="0" iso="CAD" name="Canadian Dollar" symb="$" syprf="1" dchar="." gchar="," frac="2" rate="0" mdate="0"/>
+<grp key="1" name="Banks"/>
+<account key="1" pos="1" curr="1" name="Chequing" initial="100" grp="1"/>
+<account key="2" pos="2" curr="1" name="Savings" initial="0" grp="1"/>
+<account key="3" pos="3" curr="1" name="Wallet" initial="20"/>
+<account key="4" flags="2" pos="4" curr="1" name="Closed" initial="5"/>
+<ope date="737791" amount="-10" account="1" st="2"/>
+<ope date="737792" amount="-20" account="1" st="1"/>
+<ope date="737793" amount="-30" account="1" dst_account="2" kxfer="1"/>
+<ope date="737793" amount="30" account="2" dst_account="1" kxfer="1"/>
+<ope date="737794" amount="-40" account="1" dst_account="3" kxfer="2"/>
+<ope date="737795" amount="-99" account="1" st="4"/>
+<ope date="737830" amount="-5" account="3"/>
+</homebank>
+"#;
+        HomeBankDb::from_xml(input.as_bytes())
+    }
+
+    #[test]
+    fn balances_by_group() {
+        let query = QueryBalance::new(
+            NaiveDate::from_ymd_opt(2021, 1, 31),
+            TransactionStatus::Cleared,
+        );
+        let chequing = Balances::new(Money::from(70), Money::from(0), Money::from(0));
+        let savings = Balances::new(Money::ZERO, Money::from(30), Money::from(30));
+        // only one side of the transfer into the wallet was recorded
+        let wallet = Balances::new(Money::from(20), Money::from(60), Money::from(55));
+        let expected = vec![
+            GroupBalance::new(
+                Some("Banks".to_string()),
+                vec![
+                    AccountBalance::new("Chequing", 1, chequing),
+                    AccountBalance::new("Savings", 1, savings),
+                ],
+                chequing + savings,
+                false,
+            ),
+            GroupBalance::new(
+                None,
+                vec![AccountBalance::new("Wallet", 1, wallet)],
+                wallet,
+                false,
+            ),
+        ];
+
+        assert_eq!(expected, query.exec(&test_db()));
+    }
+
+    #[test]
+    fn reconciled_balances() {
+        let query = QueryBalance::new(
+            NaiveDate::from_ymd_opt(2021, 1, 31),
+            TransactionStatus::Reconciled,
+        );
+        let observed: Vec<Money> = query
+            .exec(&test_db())
+            .iter()
+            .map(|grp| grp.total().cleared())
+            .collect();
+
+        assert_eq!(vec![Money::from(90), Money::from(20)], observed);
+    }
+}
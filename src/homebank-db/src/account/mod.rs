@@ -1,11 +1,19 @@
 //! Chequing, savings, and other types of financial accounts.
 
 pub mod account_error;
+pub mod account_flags;
 pub mod account_query;
 pub mod account_struct;
 pub mod account_type;
+pub mod balance_query;
+pub mod networth_query;
+pub mod register_query;
 
 pub use account_struct::Account;
 pub use account_error::AccountError;
+pub use account_flags::AccountFlags;
 pub use account_query::QueryAccounts;
 pub use account_type::AccountType;
+pub use balance_query::QueryBalance;
+pub use networth_query::QueryNetWorth;
+pub use register_query::QueryRegister;
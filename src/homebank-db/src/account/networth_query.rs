@@ -0,0 +1,263 @@
+//! Net worth across every [`Account`][crate::account::account_struct::Account] in the [`HomeBankDb`] over time.
+
+use crate::{interval::ReportPeriods, Account, HomeBankDb, Interval, Money, Query, Transaction};
+use chrono::{Duration, NaiveDate};
+use clap::Parser;
+use std::collections::BTreeMap;
+
+/// Query the net worth at the end of each period, like a balance sheet.
+#[derive(Debug, Parser)]
+pub struct QueryNetWorth {
+    /// The periods to report the net worth for.
+    #[clap(flatten)]
+    periods: ReportPeriods,
+}
+
+impl QueryNetWorth {
+    /// Create a new query for net worth over time
+    pub fn new(
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+        interval: Interval,
+    ) -> Self {
+        Self {
+            periods: ReportPeriods::new(date_from, date_to, interval),
+        }
+    }
+
+    /// Retrieve the periods to report the net worth for
+    fn periods(&self) -> &ReportPeriods {
+        &self.periods
+    }
+}
+
+/// The assets and liabilities of a set of [`Account`s][crate::account::account_struct::Account].
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct NetWorth {
+    /// Total balance of the accounts that hold assets.
+    assets: Money,
+
+    /// Total amount owed on the accounts that hold liabilities, as a positive amount.
+    liabilities: Money,
+}
+
+impl NetWorth {
+    /// Create a new net worth
+    pub fn new(assets: Money, liabilities: Money) -> Self {
+        Self {
+            assets,
+            liabilities,
+        }
+    }
+
+    /// Retrieve the total balance of the accounts that hold assets
+    pub fn assets(&self) -> Money {
+        self.assets
+    }
+
+    /// Retrieve the total amount owed on the accounts that hold liabilities
+    pub fn liabilities(&self) -> Money {
+        self.liabilities
+    }
+
+    /// Retrieve the assets minus the liabilities
+    pub fn net(&self) -> Money {
+        self.assets - self.liabilities
+    }
+
+    /// Add an account balance to the assets or the liabilities, depending on the type of account.
+    fn add_balance(&mut self, acct: &Account, balance: Money) {
+        if acct.atype().is_liability() {
+            self.liabilities -= balance;
+        } else {
+            self.assets += balance;
+        }
+    }
+}
+
+/// The net worth at the end of a period, in total and for each [`Group`][crate::group::group_struct::Group] of accounts.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetWorthSummary {
+    /// Last day of the period.
+    date: NaiveDate,
+
+    /// Net worth across every account, in the base currency.
+    total: NetWorth,
+
+    /// Net worth of the accounts in each group, in the base currency.
+    /// Accounts that aren't in a group are listed last, with no name.
+    groups: Vec<(Option<String>, NetWorth)>,
+
+    /// Whether any of the balances were converted from another currency.
+    converted: bool,
+}
+
+impl NetWorthSummary {
+    /// Create a new net worth summary
+    pub fn new(
+        date: NaiveDate,
+        total: NetWorth,
+        groups: Vec<(Option<String>, NetWorth)>,
+        converted: bool,
+    ) -> Self {
+        Self {
+            date,
+            total,
+            groups,
+            converted,
+        }
+    }
+
+    /// Retrieve the last day of the period
+    pub fn date(&self) -> &NaiveDate {
+        &self.date
+    }
+
+    /// Retrieve the net worth across every account
+    pub fn total(&self) -> &NetWorth {
+        &self.total
+    }
+
+    /// Retrieve the net worth of the accounts in each group
+    pub fn groups(&self) -> &[(Option<String>, NetWorth)] {
+        &self.groups
+    }
+
+    /// Check if any of the balances were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+impl Query for QueryNetWorth {
+    type T = NetWorthSummary;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let base = db.properties().currency();
+        let period_ends: Vec<NaiveDate> = self
+            .periods()
+            .periods()
+            .iter()
+            .map(|(_, end)| *end - Duration::days(1))
+            .collect();
+
+        // accounts can be left out of reports, like in HomeBank
+        let accounts: Vec<(&Account, Option<String>, Vec<Transaction>)> = db
+            .accounts()
+            .values()
+            .filter(|acct| !acct.is_excluded_from_reports())
+            .map(|acct| {
+                let grp_name = acct
+                    .group()
+                    .and_then(|idx| db.groups().get(&idx))
+                    .map(|grp| grp.name().to_string());
+                let transactions = acct
+                    .transactions(db)
+                    .into_iter()
+                    .filter(|tr| tr.counts_towards_balance())
+                    .collect();
+                (acct, grp_name, transactions)
+            })
+            .collect();
+
+        period_ends
+            .iter()
+            .map(|date| {
+                let mut total = NetWorth::default();
+                let mut groups: BTreeMap<(bool, Option<String>), NetWorth> = BTreeMap::new();
+                let mut converted = false;
+
+                for (acct, grp_name, transactions) in &accounts {
+                    let balance = acct.initial_amount()
+                        + transactions
+                            .iter()
+                            .filter(|tr| tr.date() <= date)
+                            .map(|tr| tr.total())
+                            .sum::<Money>();
+                    // accounts that can't be converted into the base currency are left out
+                    let balance = match db.convert_for_total(balance, acct.currency(), base) {
+                        Some((amount, was_converted)) => {
+                            converted |= was_converted;
+                            amount
+                        }
+                        None => continue,
+                    };
+
+                    total.add_balance(acct, balance);
+                    // accounts without a group are listed last
+                    groups
+                        .entry((grp_name.is_none(), grp_name.clone()))
+                        .or_default()
+                        .add_balance(acct, balance);
+                }
+
+                let groups = groups
+                    .into_iter()
+                    .map(|((_, name), worth)| (name, worth))
+                    .collect();
+
+                NetWorthSummary::new(*date, total, groups, converted)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn monthly_net_worth() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<grp key="1" name="Banks"/>
+<account key="1" pos="1" type="6" name="Chequing" initial="100" grp="1"/>
+<account key="2" pos="2" type="4" name="Credit card" initial="0" grp="1"/>
+<account key="3" pos="3" type="3" name="House" initial="1000"/>
+<account key="4" flags="64" pos="4" type="1" name="Not reported" initial="500"/>
+<ope date="737795" amount="-30" account="2"/>
+<ope date="737830" amount="-30" account="1" dst_account="2" kxfer="1"/>
+<ope date="737830" amount="30" account="2" dst_account="1" kxfer="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryNetWorth::new(
+            Some(ymd(2021, 1, 1)),
+            Some(ymd(2021, 3, 1)),
+            Interval::Month,
+        );
+        let expected = vec![
+            NetWorthSummary::new(
+                ymd(2021, 1, 31),
+                NetWorth::new(Money::from(1100), Money::from(30)),
+                vec![
+                    (
+                        Some("Banks".to_string()),
+                        NetWorth::new(Money::from(100), Money::from(30)),
+                    ),
+                    (None, NetWorth::new(Money::from(1000), Money::ZERO)),
+                ],
+                false,
+            ),
+            NetWorthSummary::new(
+                ymd(2021, 2, 28),
+                NetWorth::new(Money::from(1070), Money::ZERO),
+                vec![
+                    (
+                        Some("Banks".to_string()),
+                        NetWorth::new(Money::from(70), Money::ZERO),
+                    ),
+                    (None, NetWorth::new(Money::from(1000), Money::ZERO)),
+                ],
+                false,
+            ),
+        ];
+
+        assert_eq!(expected, query.exec(&db));
+        assert_eq!(Money::from(1070), expected[0].total().net());
+    }
+}
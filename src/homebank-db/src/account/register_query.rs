@@ -0,0 +1,170 @@
+//! The register of [`Transaction`s][crate::transaction::transaction_struct::Transaction] in an [`Account`][crate::account::account_struct::Account], with a running balance.
+
+use crate::{HomeBankDb, Money, Query, Transaction};
+use clap::Parser;
+use regex::Regex;
+
+/// List the [`Transaction`s][crate::transaction::transaction_struct::Transaction] in an [`Account`][crate::account::account_struct::Account] with a running balance, like HomeBank's register.
+#[derive(Debug, Parser)]
+pub struct QueryRegister {
+    /// Name of the account(s).
+    #[clap(value_name = "regex")]
+    account: Regex,
+
+    /// Show each line of a split transaction on its own row.
+    #[clap(short = 's', long = "split")]
+    split: bool,
+}
+
+impl QueryRegister {
+    /// Create a new query for an account register
+    pub fn new(account: Regex, split: bool) -> Self {
+        Self { account, split }
+    }
+
+    /// Retrieve the regular expression for the account name
+    fn account(&self) -> &Regex {
+        &self.account
+    }
+
+    /// Retrieve whether split transactions are shown line by line
+    fn split(&self) -> bool {
+        self.split
+    }
+}
+
+/// A row in the register of an [`Account`][crate::account::account_struct::Account].
+#[derive(Debug, PartialEq, Clone)]
+pub struct RegisterEntry {
+    /// The transaction, or a single line of a split transaction.
+    transaction: Transaction,
+
+    /// Balance of the account after the transaction.
+    balance: Money,
+}
+
+impl RegisterEntry {
+    /// Create a new register entry
+    pub fn new(transaction: &Transaction, balance: Money) -> Self {
+        Self {
+            transaction: transaction.clone(),
+            balance,
+        }
+    }
+
+    /// Retrieve the transaction, or the single line of a split transaction
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Retrieve the balance of the account after the transaction
+    pub fn balance(&self) -> Money {
+        self.balance
+    }
+}
+
+impl Query for QueryRegister {
+    type T = RegisterEntry;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let mut accounts: Vec<_> = db
+            .accounts()
+            .values()
+            .filter(|acct| self.account().is_match(acct.name()))
+            .collect();
+        accounts.sort_by_key(|acct| (acct.position(), acct.name().to_string()));
+
+        accounts
+            .iter()
+            .flat_map(|acct| {
+                let mut balance = acct.initial_amount();
+
+                acct.transactions(db)
+                    .iter()
+                    .flat_map(|tr| {
+                        if self.split() && tr.is_split() {
+                            (0..tr.num_splits())
+                                .filter_map(|i| tr.subset(&[i]))
+                                .collect()
+                        } else {
+                            vec![tr.clone()]
+                        }
+                    })
+                    .map(|tr| {
+                        if tr.counts_towards_balance() {
+                            balance += *tr.total();
+                        }
+
+                        RegisterEntry::new(&tr, balance)
+                    })
+                    .collect::<Vec<RegisterEntry>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_exec(query: QueryRegister, expected: Vec<(Money, Money)>) {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" pos="1" name="Chequing" initial="100"/>
+<account key="2" pos="2" name="Savings" initial="0"/>
+<ope date="737792" amount="-20" account="1" st="1"/>
+<ope date="737791" amount="-10" account="1" st="2"/>
+<ope date="737793" amount="-30" account="1" flags="256" scat="1||2" samt="-25||-5" smem="||"/>
+<ope date="737794" amount="-99" account="1" st="4"/>
+<ope date="737795" amount="-40" account="1" dst_account="2" kxfer="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let observed: Vec<(Money, Money)> = query
+            .exec(&db)
+            .iter()
+            .map(|entry| (*entry.transaction().total(), entry.balance()))
+            .collect();
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn running_balance() {
+        let query = QueryRegister::new(Regex::new("Chequing").unwrap(), false);
+        let expected = vec![
+            (Money::from(-10), Money::from(90)),
+            (Money::from(-20), Money::from(70)),
+            (Money::from(-30), Money::from(40)),
+            // void transactions don't change the balance
+            (Money::from(-99), Money::from(40)),
+            (Money::from(-40), Money::from(0)),
+        ];
+
+        check_exec(query, expected);
+    }
+
+    #[test]
+    fn split_lines() {
+        let query = QueryRegister::new(Regex::new("Chequing").unwrap(), true);
+        let expected = vec![
+            (Money::from(-10), Money::from(90)),
+            (Money::from(-20), Money::from(70)),
+            (Money::from(-25), Money::from(45)),
+            (Money::from(-5), Money::from(40)),
+            (Money::from(-99), Money::from(40)),
+            (Money::from(-40), Money::from(0)),
+        ];
+
+        check_exec(query, expected);
+    }
+
+    #[test]
+    fn other_side_of_transfer() {
+        let query = QueryRegister::new(Regex::new("Savings").unwrap(), false);
+        let expected = vec![(Money::from(40), Money::from(40))];
+
+        check_exec(query, expected);
+    }
+}
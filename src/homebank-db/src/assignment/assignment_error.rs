@@ -0,0 +1,35 @@
+//! Errors when parsing [`Assignment`s][crate::assignment::assignment_struct::Assignment] from the HomeBank XML file.
+
+use thiserror::Error;
+
+/// Errors when parsing [`Assignment`s][crate::assignment::assignment_struct::Assignment] from the HomeBank XML file.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AssignmentError {
+    /// When the key for the assignment is invalid.
+    #[error("Invalid assignment key.")]
+    InvalidKey,
+
+    /// When the flags on the assignment are invalid.
+    #[error("Invalid assignment flags. Must be a `usize` type.")]
+    InvalidFlags,
+
+    /// When the position of the assignment is invalid.
+    #[error("Invalid assignment position. Must be a `usize` type.")]
+    InvalidPosition,
+
+    /// When the field the assignment searches is invalid.
+    #[error("Invalid assignment field. Must be 0-1 or 'memo' or 'payee'.")]
+    InvalidField,
+
+    /// When the key for the assigned payee is invalid.
+    #[error("Invalid assigned payee key.")]
+    InvalidPayee,
+
+    /// When the key for the assigned category is invalid.
+    #[error("Invalid assigned category key.")]
+    InvalidCategory,
+
+    /// When the assigned pay mode is invalid.
+    #[error("Invalid assigned pay mode. Must be 0-10.")]
+    InvalidPayMode,
+}
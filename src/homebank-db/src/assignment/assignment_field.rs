@@ -0,0 +1,58 @@
+//! Which field of a [`Transaction`][crate::transaction::transaction_struct::Transaction] an [`Assignment`][crate::assignment::assignment_struct::Assignment] searches.
+
+use super::AssignmentError;
+use std::{fmt, str::FromStr};
+
+/// Which field of a [`Transaction`][crate::transaction::transaction_struct::Transaction] an [`Assignment`][crate::assignment::assignment_struct::Assignment] searches.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum AssignmentField {
+    /// The memo of the transaction.
+    #[default]
+    Memo,
+
+    /// The name of the transaction's [`Payee`][crate::payee::payee_struct::Payee].
+    Payee,
+}
+
+impl TryFrom<usize> for AssignmentField {
+    type Error = AssignmentError;
+
+    fn try_from(u: usize) -> Result<Self, Self::Error> {
+        match u {
+            0 => Ok(AssignmentField::Memo),
+            1 => Ok(AssignmentField::Payee),
+            _ => Err(AssignmentError::InvalidField),
+        }
+    }
+}
+
+impl From<AssignmentField> for usize {
+    fn from(field: AssignmentField) -> Self {
+        match field {
+            AssignmentField::Memo => 0,
+            AssignmentField::Payee => 1,
+        }
+    }
+}
+
+impl fmt::Display for AssignmentField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AssignmentField::Memo => "memo",
+            AssignmentField::Payee => "payee",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for AssignmentField {
+    type Err = AssignmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" | "Memo" | "memo" | "m" => Ok(AssignmentField::Memo),
+            "1" | "Payee" | "payee" | "p" => Ok(AssignmentField::Payee),
+            _ => Err(AssignmentError::InvalidField),
+        }
+    }
+}
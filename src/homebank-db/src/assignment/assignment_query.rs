@@ -0,0 +1,47 @@
+//! Options for filtering [`Assignment`s][crate::assignment::assignment_struct::Assignment] from the [`HomeBankDb`].
+
+use crate::{Assignment, HomeBankDb, Query};
+use clap::Parser;
+use regex::Regex;
+
+/// Options for filtering [`Assignment`s][crate::assignment::assignment_struct::Assignment] from the [`HomeBankDb`].
+#[derive(Debug, Parser)]
+#[clap(
+    name = "assignments",
+    visible_alias = "A",
+    about = "Query automatic assignment rules"
+)]
+pub struct QueryAssignments {
+    /// Text that the rule searches for.
+    #[clap(value_name = "regex")]
+    search: Option<Regex>,
+}
+
+impl QueryAssignments {
+    /// Retrieve the regular expression for the search text
+    fn search(&self) -> &Option<Regex> {
+        &self.search
+    }
+}
+
+impl Query for QueryAssignments {
+    type T = Assignment;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let mut filt_assignments: Vec<Assignment> = db
+            .assignments()
+            .values()
+            // filter out assignments that don't match the regex
+            .filter(|&asg| match self.search() {
+                Some(re) => re.is_match(asg.search()),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        // list the rules in the order they are tried
+        filt_assignments.sort_by_key(|asg| (asg.pos(), asg.key()));
+
+        filt_assignments
+    }
+}
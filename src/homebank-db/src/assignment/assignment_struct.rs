@@ -0,0 +1,475 @@
+//! Rules that automatically assign a payee, [`Category`][crate::category::category_struct::Category], or payment method to [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+
+use super::{AssignmentError, AssignmentField};
+use crate::{
+    db::db_writer::xml_attribute, transaction::TransactionComplexity, HomeBankDb, PayMode,
+    Transaction,
+};
+use regex::RegexBuilder;
+use std::str::FromStr;
+use xml::attribute::OwnedAttribute;
+
+/// Flag for matching the search text case-sensitively.
+const ASGF_EXACT: usize = 1 << 0;
+
+/// Flag for assigning the payee.
+const ASGF_DOPAY: usize = 1 << 1;
+
+/// Flag for assigning the category.
+const ASGF_DOCAT: usize = 1 << 2;
+
+/// Flag for assigning the payment method.
+const ASGF_DOMOD: usize = 1 << 3;
+
+/// Flag for treating the search text as a regular expression.
+const ASGF_REGEX: usize = 1 << 8;
+
+/// Flag for replacing a payee that is already set.
+const ASGF_OVWPAY: usize = 1 << 9;
+
+/// Flag for replacing a category that is already set.
+const ASGF_OVWCAT: usize = 1 << 10;
+
+/// Flag for replacing a payment method that is already set.
+const ASGF_OVWMOD: usize = 1 << 11;
+
+/// A rule that automatically assigns a payee, [`Category`][crate::category::category_struct::Category], or payment method to matching [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Assignment {
+    /// Unique key for the assignment in the database.
+    key: usize,
+
+    /// Flags controlling how the rule matches and what it assigns.
+    flags: usize,
+
+    /// Position of the rule, which decides the order rules are tried in.
+    pos: usize,
+
+    /// Which field of the transaction is searched.
+    field: AssignmentField,
+
+    /// Text or regular expression to search for.
+    search: String,
+
+    /// User-provided notes.
+    notes: Option<String>,
+
+    /// [`Payee`][crate::payee::payee_struct::Payee] to assign.
+    payee: Option<usize>,
+
+    /// [`Category`][crate::category::category_struct::Category] to assign.
+    category: Option<usize>,
+
+    /// Payment method to assign.
+    paymode: Option<PayMode>,
+}
+
+impl Assignment {
+    /// Create an empty [`Assignment`].
+    pub fn empty() -> Self {
+        Self {
+            key: 0,
+            flags: 0,
+            pos: 0,
+            field: AssignmentField::default(),
+            search: "".to_string(),
+            notes: None,
+            payee: None,
+            category: None,
+            paymode: None,
+        }
+    }
+
+    /// Retrieve the assignment's key from the database.
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Retrieve the position of the rule, which decides the order rules are tried in.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Retrieve which field of the transaction is searched.
+    pub fn field(&self) -> &AssignmentField {
+        &self.field
+    }
+
+    /// Retrieve the text or regular expression that is searched for.
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    /// Retrieve the notes on the rule.
+    pub fn notes(&self) -> &Option<String> {
+        &self.notes
+    }
+
+    /// Retrieve the [`Payee`][crate::payee::payee_struct::Payee] the rule assigns, if any.
+    pub fn payee(&self) -> Option<usize> {
+        self.payee.filter(|_| self.flags & ASGF_DOPAY != 0)
+    }
+
+    /// Retrieve the [`Category`][crate::category::category_struct::Category] the rule assigns, if any.
+    pub fn category(&self) -> Option<usize> {
+        self.category.filter(|_| self.flags & ASGF_DOCAT != 0)
+    }
+
+    /// Retrieve the payment method the rule assigns, if any.
+    pub fn paymode(&self) -> Option<PayMode> {
+        self.paymode.filter(|_| self.flags & ASGF_DOMOD != 0)
+    }
+
+    /// Check if the search text is matched case-sensitively.
+    pub fn is_exact(&self) -> bool {
+        self.flags & ASGF_EXACT != 0
+    }
+
+    /// Check if the search text is a regular expression.
+    pub fn is_regex(&self) -> bool {
+        self.flags & ASGF_REGEX != 0
+    }
+
+    /// Check if the rule matches some text.
+    ///
+    /// Like HomeBank, the search text can appear anywhere in the text, and only matches case-sensitively if the rule is exact.
+    /// An empty search text never matches.
+    pub fn is_text_match(&self, text: &str) -> bool {
+        if self.search.is_empty() {
+            return false;
+        }
+
+        if self.is_regex() {
+            match RegexBuilder::new(&self.search)
+                .case_insensitive(!self.is_exact())
+                .build()
+            {
+                Ok(re) => re.is_match(text),
+                Err(_) => false,
+            }
+        } else if self.is_exact() {
+            text.contains(&self.search)
+        } else {
+            text.to_lowercase().contains(&self.search.to_lowercase())
+        }
+    }
+
+    /// Check if the rule matches a [`Transaction`].
+    pub fn is_match(&self, tr: &Transaction, db: &HomeBankDb) -> bool {
+        let text = match self.field {
+            AssignmentField::Memo => tr.memo().clone(),
+            AssignmentField::Payee => tr.payee_name(db),
+        };
+
+        match text {
+            Some(t) => self.is_text_match(&t),
+            None => false,
+        }
+    }
+
+    /// Apply the rule to a [`Transaction`], the way HomeBank does.
+    ///
+    /// Fields that are already set are only replaced if the rule overwrites them,
+    /// and the category of a split transaction is never changed.
+    /// Returns whether the transaction changed.
+    pub fn apply(&self, tr: &mut Transaction) -> bool {
+        let mut changed = false;
+
+        if let Some(payee) = self.payee() {
+            if tr.payee().is_none() || self.flags & ASGF_OVWPAY != 0 {
+                changed |= tr.payee() != &Some(payee);
+                *tr.mut_payee() = Some(payee);
+            }
+        }
+
+        if let (Some(cat), TransactionComplexity::Simple(simple)) =
+            (self.category(), tr.mut_complexity())
+        {
+            if simple.category().is_none() || self.flags & ASGF_OVWCAT != 0 {
+                changed |= simple.category() != &Some(cat);
+                *simple.mut_category() = Some(cat);
+            }
+        }
+
+        if let Some(pm) = self.paymode() {
+            if tr.pay_mode() == &PayMode::None || self.flags & ASGF_OVWMOD != 0 {
+                changed |= tr.pay_mode() != &pm;
+                *tr.mut_pay_mode() = pm;
+            }
+        }
+
+        changed
+    }
+}
+
+impl Default for Assignment {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl TryFrom<Vec<OwnedAttribute>> for Assignment {
+    type Error = AssignmentError;
+
+    fn try_from(v: Vec<OwnedAttribute>) -> Result<Self, Self::Error> {
+        let mut asg = Self::default();
+
+        for i in v {
+            match i.name.local_name.as_str() {
+                "key" => {
+                    asg.key = match usize::from_str(&i.value) {
+                        Ok(idx) => idx,
+                        Err(_) => return Err(AssignmentError::InvalidKey),
+                    }
+                }
+                "flags" => {
+                    asg.flags = match usize::from_str(&i.value) {
+                        Ok(f) => f,
+                        Err(_) => return Err(AssignmentError::InvalidFlags),
+                    }
+                }
+                "pos" => {
+                    asg.pos = match usize::from_str(&i.value) {
+                        Ok(p) => p,
+                        Err(_) => return Err(AssignmentError::InvalidPosition),
+                    }
+                }
+                "field" => {
+                    asg.field = match usize::from_str(&i.value) {
+                        Ok(f) => AssignmentField::try_from(f)?,
+                        Err(_) => return Err(AssignmentError::InvalidField),
+                    }
+                }
+                "name" => {
+                    // older versions of HomeBank wrote out missing search text as `(null)`
+                    asg.search = match i.value.as_str() {
+                        "(null)" => "".to_string(),
+                        s => s.to_string(),
+                    }
+                }
+                "notes" => {
+                    asg.notes = match i.value.as_str() {
+                        "" => None,
+                        s => Some(s.to_string()),
+                    }
+                }
+                "payee" => {
+                    asg.payee = match usize::from_str(&i.value) {
+                        Ok(idx) => Some(idx),
+                        Err(_) => return Err(AssignmentError::InvalidPayee),
+                    }
+                }
+                "category" => {
+                    asg.category = match usize::from_str(&i.value) {
+                        Ok(idx) => Some(idx),
+                        Err(_) => return Err(AssignmentError::InvalidCategory),
+                    }
+                }
+                "paymode" => {
+                    asg.paymode = match usize::from_str(&i.value) {
+                        Ok(pm) => match PayMode::try_from(pm) {
+                            Ok(pm) => Some(pm),
+                            Err(_) => return Err(AssignmentError::InvalidPayMode),
+                        },
+                        Err(_) => return Err(AssignmentError::InvalidPayMode),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(asg)
+    }
+}
+
+impl From<&Assignment> for Vec<OwnedAttribute> {
+    fn from(asg: &Assignment) -> Self {
+        let mut v = vec![xml_attribute("key", asg.key)];
+
+        // HomeBank leaves out numbers of 0 and empty text
+        if asg.flags != 0 {
+            v.push(xml_attribute("flags", asg.flags));
+        }
+        if asg.pos != 0 {
+            v.push(xml_attribute("pos", asg.pos));
+        }
+        if asg.field != AssignmentField::Memo {
+            v.push(xml_attribute("field", usize::from(asg.field)));
+        }
+        if !asg.search.is_empty() {
+            v.push(xml_attribute("name", &asg.search));
+        }
+        if let Some(notes) = &asg.notes {
+            v.push(xml_attribute("notes", notes));
+        }
+        if let Some(payee) = asg.payee {
+            v.push(xml_attribute("payee", payee));
+        }
+        if let Some(cat) = asg.category {
+            v.push(xml_attribute("category", cat));
+        }
+        if let Some(pm) = asg.paymode {
+            v.push(xml_attribute("paymode", usize::from(pm)));
+        }
+
+        v
+    }
+}
+
+/// Find the first [`Assignment`] that matches a [`Transaction`], trying them in the same order as HomeBank.
+pub fn matching_assignment<'a>(db: &'a HomeBankDb, tr: &Transaction) -> Option<&'a Assignment> {
+    let mut assignments: Vec<&Assignment> = db.assignments().values().collect();
+    assignments.sort_by_key(|asg| (asg.pos(), asg.key()));
+
+    assignments.into_iter().find(|asg| asg.is_match(tr, db))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xml::{reader::XmlEvent, EventReader};
+
+    /// Parse the first element of an XML string.
+    #[track_caller]
+    fn parse_first_element<T: TryFrom<Vec<OwnedAttribute>>>(input: &str) -> T {
+        let mut reader = EventReader::from_str(input);
+        let (_start, first) = (reader.next(), reader.next());
+
+        if let Ok(XmlEvent::StartElement { attributes, .. }) = first {
+            match T::try_from(attributes) {
+                Ok(parsed) => parsed,
+                Err(_) => panic!("Could not parse `{input}`"),
+            }
+        } else {
+            panic!("Incorrect string passed into check. `{:#?}`", first);
+        }
+    }
+
+    #[track_caller]
+    fn check_text_match(asg: &str, text: &str, expected: bool) {
+        let asg: Assignment = parse_first_element(asg);
+
+        assert_eq!(expected, asg.is_text_match(text));
+    }
+
+    #[test]
+    fn parse_assignment() {
+        let observed: Assignment = parse_first_element(
+            r#"<asg key="2" flags="1542" pos="3" field="1" name="Grocer" payee="4" category="5" paymode="3"/>"#,
+        );
+        let expected = Assignment {
+            key: 2,
+            flags: 1542,
+            pos: 3,
+            field: AssignmentField::Payee,
+            search: "Grocer".to_string(),
+            notes: None,
+            payee: Some(4),
+            category: Some(5),
+            paymode: Some(PayMode::Cash),
+        };
+
+        assert_eq!(expected, observed);
+        // only the payee and category are assigned, not the pay mode
+        assert_eq!(Some(4), observed.payee());
+        assert_eq!(Some(5), observed.category());
+        assert_eq!(None, observed.paymode());
+    }
+
+    #[test]
+    fn parse_invalid_field() {
+        let input = r#"<asg key="1" field="4"/>"#;
+        let mut reader = EventReader::from_str(input);
+        let (_start, first) = (reader.next(), reader.next());
+
+        if let Ok(XmlEvent::StartElement { attributes, .. }) = first {
+            assert_eq!(
+                Err(AssignmentError::InvalidField),
+                Assignment::try_from(attributes)
+            );
+        }
+    }
+
+    #[test]
+    fn text_match_ignores_case() {
+        check_text_match(r#"<asg name="netflix"/>"#, "NETFLIX.COM 1234", true);
+    }
+
+    #[test]
+    fn text_match_exact() {
+        check_text_match(r#"<asg flags="1" name="netflix"/>"#, "NETFLIX.COM 1234", false);
+        check_text_match(r#"<asg flags="1" name="NETFLIX"/>"#, "NETFLIX.COM 1234", true);
+    }
+
+    #[test]
+    fn text_match_regex() {
+        check_text_match(r#"<asg flags="256" name="^net.*\d+$"/>"#, "NETFLIX.COM 1234", true);
+        check_text_match(r#"<asg flags="257" name="^net.*\d+$"/>"#, "NETFLIX.COM 1234", false);
+        check_text_match(r#"<asg flags="256" name="(unclosed"/>"#, "(unclosed", false);
+    }
+
+    #[test]
+    fn empty_search_never_matches() {
+        check_text_match(r#"<asg name=""/>"#, "anything", false);
+    }
+
+    #[test]
+    fn apply_keeps_existing_values() {
+        let asg: Assignment =
+            parse_first_element(r#"<asg flags="14" name="x" payee="4" category="5" paymode="3"/>"#);
+        let mut tr: Transaction =
+            parse_first_element(r#"<ope amount="-1" paymode="1" payee="2" category="3"/>"#);
+
+        assert!(!asg.apply(&mut tr));
+        assert_eq!(&Some(2), tr.payee());
+        assert_eq!(vec![&Some(3)], tr.categories());
+        assert_eq!(&PayMode::CreditCard, tr.pay_mode());
+    }
+
+    #[test]
+    fn apply_fills_missing_values() {
+        let asg: Assignment =
+            parse_first_element(r#"<asg flags="14" name="x" payee="4" category="5" paymode="3"/>"#);
+        let mut tr: Transaction = parse_first_element(r#"<ope amount="-1"/>"#);
+
+        assert!(asg.apply(&mut tr));
+        assert_eq!(&Some(4), tr.payee());
+        assert_eq!(vec![&Some(5)], tr.categories());
+        assert_eq!(&PayMode::Cash, tr.pay_mode());
+    }
+
+    #[test]
+    fn apply_overwrites_values() {
+        let asg: Assignment = parse_first_element(
+            r#"<asg flags="3598" name="x" payee="4" category="5" paymode="3"/>"#,
+        );
+        let mut tr: Transaction =
+            parse_first_element(r#"<ope amount="-1" paymode="1" payee="2" category="3"/>"#);
+
+        assert!(asg.apply(&mut tr));
+        assert_eq!(&Some(4), tr.payee());
+        assert_eq!(vec![&Some(5)], tr.categories());
+        assert_eq!(&PayMode::Cash, tr.pay_mode());
+    }
+
+    #[test]
+    fn apply_never_changes_split_categories() {
+        let asg: Assignment = parse_first_element(r#"<asg flags="1028" name="x" category="5"/>"#);
+        let mut tr: Transaction = parse_first_element(
+            r#"<ope amount="-3" scat="1||2" samt="-1||-2" smem="||"/>"#,
+        );
+
+        assert!(!asg.apply(&mut tr));
+        assert_eq!(vec![&Some(1), &Some(2)], tr.categories());
+    }
+
+    #[test]
+    fn round_trip_assignment() {
+        let original: Assignment = parse_first_element(
+            r#"<asg key="2" flags="1542" pos="3" field="1" name="Grocer" notes="weekly" payee="4" category="5" paymode="3"/>"#,
+        );
+        let written: Vec<OwnedAttribute> = (&original).into();
+
+        assert_eq!(Ok(original), Assignment::try_from(written));
+    }
+}
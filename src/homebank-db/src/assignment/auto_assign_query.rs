@@ -0,0 +1,69 @@
+//! Find the uncategorized [`Transaction`s][crate::transaction::transaction_struct::Transaction] that an [`Assignment`] would categorize.
+
+use super::matching_assignment;
+use crate::{Assignment, HomeBankDb, Query, Transaction};
+use clap::Parser;
+
+/// Find the uncategorized [`Transaction`s][crate::transaction::transaction_struct::Transaction] that an [`Assignment`] would categorize.
+#[derive(Debug, Parser)]
+pub struct QueryAutoAssign {}
+
+impl Query for QueryAutoAssign {
+    /// The transaction with the rule applied, and the rule that matched it.
+    type T = (Transaction, Assignment);
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        db.transactions()
+            .iter()
+            // transfers don't need a category, and HomeBank never assigns one to a split
+            .filter(|&tr| !tr.is_transfer() && !tr.is_split())
+            .filter(|&tr| tr.categories().iter().all(|cat| cat.is_none()))
+            .filter_map(|tr| {
+                let asg = matching_assignment(db, tr)?;
+                let mut fixed = tr.clone();
+                asg.apply(&mut fixed);
+
+                // only keep the transactions that end up categorized
+                if fixed.categories().iter().any(|cat| cat.is_some()) {
+                    Some((fixed, asg.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Money;
+
+    #[test]
+    fn fixes_uncategorized_transactions() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<pay key="1" name="Corner Grocer"/>
+<cat key="1" name="Groceries"/>
+<cat key="2" name="Streaming"/>
+<asg key="1" flags="6" pos="2" field="1" name="grocer" payee="1" category="1"/>
+<asg key="2" flags="4" pos="1" field="0" name="netflix" category="2"/>
+<asg key="3" flags="2" pos="3" field="0" name="no category" payee="1"/>
+<ope date="737495" amount="-10" account="1" payee="1"/>
+<ope date="737495" amount="-12" account="1" wording="Netflix.com"/>
+<ope date="737495" amount="-15" account="1" payee="1" category="2"/>
+<ope date="737495" amount="-20" account="1" wording="Something else"/>
+<ope date="737495" amount="-30" account="1" wording="No category here"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let observed: Vec<(Money, Option<usize>, usize)> = QueryAutoAssign {}
+            .exec(&db)
+            .iter()
+            .map(|(tr, asg)| (*tr.total(), *tr.categories()[0], asg.key()))
+            .collect();
+        let expected = vec![(Money::from(-10), Some(1), 1), (Money::from(-12), Some(2), 2)];
+
+        assert_eq!(expected, observed);
+    }
+}
@@ -0,0 +1,13 @@
+//! Rules that automatically assign a payee, [`Category`][crate::category::category_struct::Category], or payment method to [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+
+pub mod assignment_error;
+pub mod assignment_field;
+pub mod assignment_query;
+pub mod assignment_struct;
+pub mod auto_assign_query;
+
+pub use assignment_error::AssignmentError;
+pub use assignment_field::AssignmentField;
+pub use assignment_query::QueryAssignments;
+pub use assignment_struct::{matching_assignment, Assignment};
+pub use auto_assign_query::QueryAutoAssign;
@@ -1,12 +1,16 @@
 //! Query the budget in your HomeBank database.
 
-use crate::{transaction::sum_transactions, Category, HomeBankDb, Query, QueryTransactions};
-use super::{TODAY_FIRST_OF_MONTH_STR, FIRST_OF_NEXT_MONTH_STR};
+use crate::{
+    interval::{parse_date, parse_date_to, parse_date_until},
+    transaction::sum_transactions_in, Category, DateExpr, HomeBankDb, Interval, Money,
+    Query, QueryTransactions, Transaction,
+};
+use super::{TODAY, TODAY_FIRST_OF_MONTH_STR, FIRST_OF_NEXT_MONTH_STR};
 
-use chrono::NaiveDate;
+use chrono::{Datelike, Duration, Months, NaiveDate};
 use clap::Parser;
 use regex::Regex;
-use std::str::FromStr;
+use rust_decimal::Decimal;
 
 /// Query the budget in your HomeBank database.
 #[derive(Debug, Parser)]
@@ -20,7 +24,8 @@ pub struct QueryBudget {
         short = 'd',
         long = "date-from",
         default_value = &TODAY_FIRST_OF_MONTH_STR,
-        parse(try_from_str = NaiveDate::from_str),
+        parse(try_from_str = parse_date),
+        allow_hyphen_values = true,
         value_name = "date"
     )]
     date_from: NaiveDate,
@@ -30,19 +35,104 @@ pub struct QueryBudget {
         short = 'D',
         long = "date-to",
         default_value = &FIRST_OF_NEXT_MONTH_STR,
-        parse(try_from_str = NaiveDate::from_str),
+        parse(try_from_str = parse_date_to),
+        allow_hyphen_values = true,
         value_name = "date"
     )]
     date_to: NaiveDate,
+
+    /// Consider the budget in this period, instead of `--date-from` and `--date-to`.
+    /// Use a period like '2026-Q2', '2026-03', 'last-month', or 'ytd', or a relative date like '-30d' for the days since then.
+    #[clap(
+        long = "period",
+        conflicts_with_all = &["date-from", "date-to"],
+        allow_hyphen_values = true,
+        value_name = "period"
+    )]
+    period: Option<DateExpr>,
+
+    /// Convert the budgets and spending into the currency with this ISO code, instead of the base currency.
+    #[clap(long = "currency", value_name = "ISO")]
+    currency: Option<String>,
+
+    /// Show a table of the budget and spending in each month of a year, instead of progress bars.
+    #[clap(short = 'm', long = "matrix")]
+    matrix: bool,
+
+    /// Year to show in the table.
+    /// Defaults to the current year.
+    #[clap(short = 'y', long = "year", requires = "matrix", value_name = "year")]
+    year: Option<i32>,
+
+    /// Carry the unspent budget, or the overspending, of earlier months forward, like envelope budgeting.
+    #[clap(short = 'r', long = "rollover")]
+    rollover: bool,
+
+    /// Carry the budget forward from the month including this date.
+    /// Defaults to the start of the year of `--date-from`.
+    #[clap(
+        long = "rollover-from",
+        requires = "rollover",
+        parse(try_from_str = parse_date),
+        allow_hyphen_values = true,
+        value_name = "date"
+    )]
+    rollover_from: Option<NaiveDate>,
+
+    /// Project the spending in each category to the end of the month, instead of showing progress bars.
+    #[clap(short = 'p', long = "projection", conflicts_with_all = &["matrix", "rollover"])]
+    projection: bool,
+
+    /// Project the month including this date, using the spending up to and including it.
+    /// Defaults to today.
+    #[clap(
+        long = "as-of",
+        requires = "projection",
+        parse(try_from_str = parse_date_until),
+        allow_hyphen_values = true,
+        value_name = "date"
+    )]
+    as_of: Option<NaiveDate>,
+
+    /// Number of earlier months to learn how spending is spread across a month from.
+    #[clap(
+        long = "history",
+        requires = "projection",
+        default_value = "3",
+        value_name = "months"
+    )]
+    history: u32,
 }
 
 impl QueryBudget {
     /// Create a new query for budgets
-    pub fn new(name: Option<Regex>, date_from: NaiveDate, date_to: NaiveDate) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: Option<Regex>,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+        currency: Option<String>,
+        matrix: bool,
+        year: Option<i32>,
+        rollover: bool,
+        rollover_from: Option<NaiveDate>,
+        projection: bool,
+        as_of: Option<NaiveDate>,
+        history: u32,
+    ) -> Self {
         Self {
             name,
             date_from,
             date_to,
+            period: None,
+            currency,
+            matrix,
+            year,
+            rollover,
+            rollover_from,
+            projection,
+            as_of,
+            history,
         }
     }
 
@@ -52,13 +142,304 @@ impl QueryBudget {
     }
 
     /// Retrieve the earliest date that the budget is including
-    fn date_from(&self) -> &NaiveDate {
-        &self.date_from
+    fn date_from(&self) -> NaiveDate {
+        self.period.map_or(self.date_from, |period| period.start())
     }
 
     /// Retrieve the latest date that the budget is including
-    fn date_to(&self) -> &NaiveDate {
-        &self.date_to
+    fn date_to(&self) -> NaiveDate {
+        self.period.map_or(self.date_to, |period| period.end())
+    }
+
+    /// Retrieve the ISO code of the currency to convert into, if not the base currency.
+    ///
+    /// Nothing is returned by the query if the currency is missing, or if amounts can't be converted into it, so check it first with [`HomeBankDb::currency_key`].
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+
+    /// Retrieve whether to show a table of each month, instead of progress bars
+    pub fn matrix(&self) -> bool {
+        self.matrix
+    }
+
+    /// Retrieve the year to show in the table
+    pub fn year(&self) -> i32 {
+        self.year.unwrap_or_else(|| TODAY.year())
+    }
+
+    /// Retrieve whether the budget of earlier months is carried forward
+    fn rollover(&self) -> bool {
+        self.rollover
+    }
+
+    /// Retrieve the date that the budget is carried forward from
+    fn rollover_from(&self) -> NaiveDate {
+        self.rollover_from.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(self.date_from().year(), 1, 1).unwrap_or(self.date_from())
+        })
+    }
+
+    /// Retrieve whether to project the spending to the end of the month, instead of showing progress bars
+    pub fn projection(&self) -> bool {
+        self.projection
+    }
+
+    /// Retrieve the date that the spending is projected from
+    fn as_of(&self) -> NaiveDate {
+        self.as_of.unwrap_or(*TODAY)
+    }
+
+    /// Retrieve the number of earlier months that the projection learns from
+    fn history(&self) -> u32 {
+        self.history
+    }
+
+    /// Check if a category's budget is a target to earn, rather than a limit to spend.
+    ///
+    /// This is the case for income categories, or when the budget is positive.
+    fn is_income_budget(cat: &Category, allotment: Option<Money>) -> bool {
+        cat.is_income() || allotment.map_or(false, |val| val.is_positive())
+    }
+
+    /// Retrieve the categories with a budget that match the query, sorted by name
+    fn budgeted_categories(&self, db: &HomeBankDb) -> Vec<Category> {
+        let mut filt_categories: Vec<Category> = db
+            .categories()
+            .values()
+            // filter out categories that don't match the regex
+            .filter(|&cat| match self.name() {
+                Some(re) => re.is_match(&cat.full_name(db)),
+                None => true,
+            })
+            // filter out categories that don't have a budget
+            .filter(|&cat| cat.has_budget())
+            .cloned()
+            .collect();
+
+        filt_categories.sort_by_key(|a| a.full_name(db));
+
+        filt_categories
+    }
+
+    /// Retrieve the keys of a category and its subcategories, since the spending of a category includes its subcategories
+    fn category_keys(cat: &Category, db: &HomeBankDb) -> Vec<usize> {
+        let mut cat_keys = vec![cat.key()];
+        cat_keys.extend(
+            db.categories()
+                .values()
+                .filter(|sub| sub.parent_key() == Some(cat.key()))
+                .map(|sub| sub.key()),
+        );
+
+        cat_keys
+    }
+
+    /// Sum the spending in a category between two dates, and whether any of it was converted from another currency
+    fn spending(
+        &self,
+        cat: &Category,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+        db: &HomeBankDb,
+        currency: usize,
+    ) -> (Money, bool) {
+        let cat_keys = Self::category_keys(cat, db);
+
+        let transaction_query = QueryTransactions::new(
+            &Some(date_from),
+            &Some(date_to),
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+        );
+
+        let filt_transactions: Vec<Transaction> = transaction_query
+            .exec(db)
+            .iter()
+            // accounts can be left out of budgets, like in HomeBank
+            .filter(|tr| !tr.is_excluded_from_budget(db))
+            .filter_map(|tr| tr.subset_categories(&cat_keys))
+            .collect();
+
+        sum_transactions_in(&filt_transactions, db, currency)
+    }
+
+    /// Sum the unspent budget of a category over each month before the query starts, and whether any of it was converted from another currency.
+    ///
+    /// Overspending is carried forward too, so the amount has the same sign as the allotment when there is money left over.
+    fn carried(
+        &self,
+        cat: &Category,
+        db: &HomeBankDb,
+        base: usize,
+        currency: usize,
+    ) -> (Money, bool) {
+        let start = Interval::Month.start_of(&self.rollover_from());
+        let end = Interval::Month.start_of(&self.date_from());
+        let mut carried = Money::ZERO;
+        let mut converted = false;
+
+        for (date_from, date_to) in Interval::Month.periods(&start, &end) {
+            let (actual, sum_converted) = self.spending(cat, date_from, date_to, db, currency);
+            converted |= sum_converted;
+            // budgets are set in the base currency
+            let allotment = cat
+                .budget_amount(date_from.month() as usize)
+                .and_then(|val| db.convert(val, base, currency))
+                .unwrap_or(Money::ZERO);
+            carried += allotment - actual;
+        }
+
+        (carried, converted)
+    }
+
+    /// Project the spending in each category to the end of the month.
+    ///
+    /// The rest of the month is estimated from both the pace of spending so far and how much was spent over the same days in earlier months.
+    /// Scheduled transactions that are still due this month are added on top.
+    pub fn exec_projection(&self, db: &HomeBankDb) -> Vec<BudgetProjection> {
+        let base = db.properties().currency();
+        // totals can't be made in a currency that is missing or that amounts can't be converted into
+        let currency = match db.currency_key(self.currency()) {
+            Ok(key) => key,
+            Err(_) => return vec![],
+        };
+        let as_of = self.as_of();
+        let month_start = Interval::Month.start_of(&as_of);
+        let month_end = Interval::Month.next_start(&as_of);
+        let tomorrow = as_of + Duration::days(1);
+        let days_elapsed = (tomorrow - month_start).num_days();
+        let days_left = (month_end - tomorrow).num_days();
+
+        // scheduled transactions that are still due this month, in accounts that count towards budgets
+        let scheduled: Vec<Transaction> = db
+            .templates()
+            .values()
+            .flat_map(|tmpl| {
+                tmpl.occurrences_until(&(month_end - Duration::days(1)))
+                    .iter()
+                    .filter(|date| **date > as_of)
+                    .flat_map(|date| tmpl.transactions_on(date, 0))
+                    .collect::<Vec<Transaction>>()
+            })
+            .filter(|tr| tr.counts_towards_balance())
+            .filter(|tr| !tr.is_excluded_from_budget(db))
+            .collect();
+
+        self.budgeted_categories(db)
+            .iter()
+            .map(|cat| {
+                let (spent, spent_converted) =
+                    self.spending(cat, month_start, tomorrow, db, currency);
+                let mut converted = spent_converted || base != currency;
+
+                // keep up the pace of the month so far
+                let pace = spent
+                    .checked_mul(Decimal::from(days_left))
+                    .and_then(|val| val.checked_div(Decimal::from(days_elapsed)))
+                    .unwrap_or(Money::ZERO);
+
+                // spend what was spent over the rest of earlier months
+                let mut history = Money::ZERO;
+                for i in 1..=self.history() {
+                    let start = match month_start.checked_sub_months(Months::new(i)) {
+                        Some(d) => d,
+                        None => continue,
+                    };
+                    let end = Interval::Month.next_start(&start);
+                    let rest_start = start + Duration::days(days_elapsed);
+                    if rest_start < end {
+                        let (sum, sum_converted) =
+                            self.spending(cat, rest_start, end, db, currency);
+                        history += sum;
+                        converted |= sum_converted;
+                    }
+                }
+                let history = history
+                    .checked_div(Decimal::from(self.history()))
+                    .unwrap_or(pace);
+
+                // only the lines of scheduled transactions in this category, like its spending
+                let cat_keys = Self::category_keys(cat, db);
+                let cat_scheduled: Vec<Transaction> = scheduled
+                    .iter()
+                    .filter_map(|tr| tr.subset_categories(&cat_keys))
+                    .collect();
+                let (scheduled_sum, scheduled_converted) =
+                    sum_transactions_in(&cat_scheduled, db, currency);
+                converted |= scheduled_converted;
+
+                // average both estimates of the rest of the month
+                let estimate = (pace + history).checked_div(Decimal::from(2)).unwrap_or(pace);
+                let projected = spent + estimate + scheduled_sum;
+
+                // budgets are set in the base currency
+                let allotment = cat
+                    .budget_amount(month_start.month() as usize)
+                    .and_then(|val| db.convert(val, base, currency));
+
+                BudgetProjection::new(
+                    &cat.full_name(db),
+                    Self::is_income_budget(cat, allotment),
+                    allotment,
+                    spent,
+                    scheduled_sum,
+                    projected,
+                    converted,
+                )
+            })
+            .collect()
+    }
+
+    /// Compare the budget and spending of each category in each month of the year.
+    pub fn exec_matrix(&self, db: &HomeBankDb) -> Vec<BudgetMatrixRow> {
+        let base = db.properties().currency();
+        // totals can't be made in a currency that is missing or that amounts can't be converted into
+        let currency = match db.currency_key(self.currency()) {
+            Ok(key) => key,
+            Err(_) => return vec![],
+        };
+        let year = self.year();
+
+        self.budgeted_categories(db)
+            .iter()
+            .map(|cat| {
+                let mut converted = base != currency;
+                let mut ytd_variance = Money::ZERO;
+                let months = (1..=12)
+                    .filter_map(|month| {
+                        let date_from = NaiveDate::from_ymd_opt(year, month, 1)?;
+                        let date_to = match month {
+                            12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+                            _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+                        };
+
+                        let (actual, sum_converted) =
+                            self.spending(cat, date_from, date_to, db, currency);
+                        converted |= sum_converted;
+                        // budgets are set in the base currency
+                        let allotment = cat
+                            .budget_amount(month as usize)
+                            .and_then(|val| db.convert(val, base, currency))
+                            .unwrap_or(Money::ZERO);
+                        ytd_variance += actual - allotment;
+
+                        Some(BudgetMonth::new(allotment, actual, ytd_variance))
+                    })
+                    .collect();
+
+                BudgetMatrixRow::new(&cat.full_name(db), months, converted)
+            })
+            .collect()
     }
 }
 
@@ -66,25 +447,49 @@ impl QueryBudget {
 pub struct BudgetSummary {
     /// The [`Category`] name
     name: String,
+
+    /// Whether the budget is a target to earn, rather than a limit to spend.
+    income: bool,
     
     /// The total sum of [`Transaction`s][crate::transaction::transaction_struct::Transaction] over the time span provided.
-    progress: f32,
+    progress: Money,
 
     /// How much room is allotted for this [`Category`] over the time span provided.
-    allotment: Option<f32>,
+    allotment: Option<Money>,
+
+    /// How much unspent room is carried forward from earlier months, when rolling the budget over.
+    carried: Money,
 
-    /// The fraction of the spending over the allotted amount.
+    /// The fraction of the spending over the available amount, if any amount is available.
     progress_frac: Option<f32>,
+
+    /// Whether any of the amounts were converted from another currency.
+    converted: bool,
 }
 
 impl BudgetSummary {
     /// Create a new budget summary
-    pub fn new(name: &str, progress: f32, allotment: Option<f32>) -> Self {
+    pub fn new(
+        name: &str,
+        income: bool,
+        progress: Money,
+        allotment: Option<Money>,
+        carried: Money,
+        converted: bool,
+    ) -> Self {
+        let available = allotment.map(|val| val + carried);
+
         Self {
             name: name.to_string(),
+            income,
             progress,
             allotment,
-            progress_frac: allotment.map(|val| progress / val),
+            carried,
+            // there is no fraction of nothing
+            progress_frac: available
+                .filter(|val| !val.is_zero())
+                .map(|val| progress.to_f32() / val.to_f32()),
+            converted,
         }
     }
 
@@ -94,13 +499,13 @@ impl BudgetSummary {
     }
 
     /// Retrieve the progress of the budget
-    pub fn progress(&self) -> f32 {
+    pub fn progress(&self) -> Money {
         self.progress
     }
 
     /// Retrieve the progress of the budget, made positive, and rounded to the nearest integer
     pub fn progress_rounded(&self) -> u64 {
-        self.progress.abs() as u64
+        self.progress.abs().round(0).to_f64() as u64
     }
 
     /// Retrieve the progress of the budget
@@ -109,68 +514,629 @@ impl BudgetSummary {
     }
 
     /// Retrieve the allotment for the budget
-    pub fn allotment(&self) -> Option<f32> {
+    pub fn allotment(&self) -> Option<Money> {
         self.allotment
     }
 
     /// Retrieve the allotment for the budget, made positive, and rounded to the nearest integer
     pub fn allotment_rounded(&self) -> Option<u64> {
-        self.allotment.map(|val| val.abs() as u64)
+        self.allotment.map(|val| val.abs().round(0).to_f64() as u64)
+    }
+
+    /// Retrieve the unspent room carried forward from earlier months
+    pub fn carried(&self) -> Money {
+        self.carried
+    }
+
+    /// Retrieve the room available for the budget, which is the allotment plus the amount carried forward
+    pub fn available(&self) -> Option<Money> {
+        self.allotment.map(|val| val + self.carried)
+    }
+
+    /// Retrieve the room available for the budget, made positive, and rounded to the nearest integer
+    pub fn available_rounded(&self) -> Option<u64> {
+        self.available().map(|val| val.abs().round(0).to_f64() as u64)
     }
 
     /// Helper function to determine if there is a budget or not
     pub fn has_allotment(&self) -> bool {
         self.allotment.is_some()
     }
+
+    /// Check if the budget is a target to earn, rather than a limit to spend
+    pub fn is_income(&self) -> bool {
+        self.income
+    }
+
+    /// Check if more was spent than the room available for an expense budget.
+    ///
+    /// Spending is negative, so this is when the progress is below the room available.
+    /// Overspending carried forward can push the room available above zero, so the budget stays over until refunds make it up.
+    pub fn is_over_budget(&self) -> bool {
+        !self.income && self.available().map_or(false, |val| self.progress < val)
+    }
+
+    /// Check if less was earned than the target of an income budget
+    pub fn is_under_target(&self) -> bool {
+        self.income && self.available().map_or(false, |val| self.progress < val)
+    }
+
+    /// Check if any of the amounts were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+/// The budget and spending of a [`Category`] in a single month.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BudgetMonth {
+    /// How much room is allotted for the month.
+    allotment: Money,
+
+    /// The sum of [`Transaction`s][crate::transaction::transaction_struct::Transaction] in the month.
+    actual: Money,
+
+    /// The variance of every month in the year up to and including this one.
+    ytd_variance: Money,
+}
+
+impl BudgetMonth {
+    /// Create a new month of a budget
+    pub fn new(allotment: Money, actual: Money, ytd_variance: Money) -> Self {
+        Self {
+            allotment,
+            actual,
+            ytd_variance,
+        }
+    }
+
+    /// Retrieve the allotment for the month
+    pub fn allotment(&self) -> Money {
+        self.allotment
+    }
+
+    /// Retrieve the sum of transactions in the month
+    pub fn actual(&self) -> Money {
+        self.actual
+    }
+
+    /// Retrieve how much better the month did than its allotment.
+    /// This is negative when more was spent, or less was earned, than allotted.
+    pub fn variance(&self) -> Money {
+        self.actual - self.allotment
+    }
+
+    /// Retrieve the variance of every month in the year up to and including this one
+    pub fn ytd_variance(&self) -> Money {
+        self.ytd_variance
+    }
+
+    /// Check if more was spent, or less was earned, than allotted
+    pub fn is_over_budget(&self) -> bool {
+        self.variance().is_negative()
+    }
+}
+
+/// The budget and spending of a [`Category`] in each month of a year.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BudgetMatrixRow {
+    /// The [`Category`] name
+    name: String,
+
+    /// The budget and spending in each month, starting with January.
+    months: Vec<BudgetMonth>,
+
+    /// Whether any of the amounts were converted from another currency.
+    converted: bool,
+}
+
+impl BudgetMatrixRow {
+    /// Create a new row of the budget table
+    pub fn new(name: &str, months: Vec<BudgetMonth>, converted: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            months,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the [`Category`] to which the budget applies
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieve the budget and spending in each month, starting with January
+    pub fn months(&self) -> &[BudgetMonth] {
+        &self.months
+    }
+
+    /// Check if any of the amounts were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+/// The spending of a [`Category`] so far this month, and where it is projected to end up.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BudgetProjection {
+    /// The [`Category`] name
+    name: String,
+
+    /// Whether the budget is a target to earn, rather than a limit to spend.
+    income: bool,
+
+    /// How much room is allotted for this [`Category`] over the month.
+    allotment: Option<Money>,
+
+    /// The sum of [`Transaction`s][crate::transaction::transaction_struct::Transaction] so far this month.
+    spent: Money,
+
+    /// The sum of scheduled transactions that are still due this month.
+    scheduled: Money,
+
+    /// The projected sum by the end of the month, including the scheduled transactions.
+    projected: Money,
+
+    /// Whether any of the amounts were converted from another currency.
+    converted: bool,
+}
+
+impl BudgetProjection {
+    /// Create a new budget projection
+    pub fn new(
+        name: &str,
+        income: bool,
+        allotment: Option<Money>,
+        spent: Money,
+        scheduled: Money,
+        projected: Money,
+        converted: bool,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            income,
+            allotment,
+            spent,
+            scheduled,
+            projected,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the [`Category`] to which the budget applies
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieve the allotment for the month
+    pub fn allotment(&self) -> Option<Money> {
+        self.allotment
+    }
+
+    /// Retrieve the sum of transactions so far this month
+    pub fn spent(&self) -> Money {
+        self.spent
+    }
+
+    /// Retrieve the sum of scheduled transactions that are still due this month
+    pub fn scheduled(&self) -> Money {
+        self.scheduled
+    }
+
+    /// Retrieve the projected sum by the end of the month
+    pub fn projected(&self) -> Money {
+        self.projected
+    }
+
+    /// Check if the budget is a target to earn, rather than a limit to spend
+    pub fn is_income(&self) -> bool {
+        self.income
+    }
+
+    /// Check if the spending so far is already over the allotment of an expense budget
+    pub fn is_over(&self) -> bool {
+        !self.income && self.allotment.map_or(false, |val| self.spent.abs() > val.abs())
+    }
+
+    /// Check if the spending is projected to go over the allotment of an expense budget by the end of the month
+    pub fn is_projected_over(&self) -> bool {
+        !self.income && self.allotment.map_or(false, |val| self.projected.abs() > val.abs())
+    }
+
+    /// Check if the earnings are projected to fall short of the target of an income budget by the end of the month
+    pub fn is_projected_short(&self) -> bool {
+        self.income && self.allotment.map_or(false, |val| self.projected < val)
+    }
+
+    /// Check if any of the amounts were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
 }
 
 impl Query for QueryBudget {
     type T = BudgetSummary;
 
     fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
-        let mut filt_categories: Vec<Category> = db
-            .categories()
-            .values()
-            // filter out categories that don't match the regex
-            .filter(|&cat| match self.name() {
-                Some(re) => re.is_match(&cat.full_name(db)),
-                None => true,
+        let base = db.properties().currency();
+        // totals can't be made in a currency that is missing or that amounts can't be converted into
+        let currency = match db.currency_key(self.currency()) {
+            Ok(key) => key,
+            Err(_) => return vec![],
+        };
+
+        let budget_spent: Vec<BudgetSummary> = self
+            .budgeted_categories(db)
+            .iter()
+            .map(|cat| {
+                let (sum, sum_converted) =
+                    self.spending(cat, self.date_from(), self.date_to(), db, currency);
+                // budgets are set in the base currency
+                let allotment = cat
+                    .budget_amount_over_interval(self.date_from(), self.date_to())
+                    .and_then(|val| db.convert(val, base, currency));
+                let (carried, carried_converted) = if self.rollover() {
+                    self.carried(cat, db, base, currency)
+                } else {
+                    (Money::ZERO, false)
+                };
+                let converted = sum_converted
+                    || carried_converted
+                    || (allotment.is_some() && base != currency);
+
+                BudgetSummary::new(
+                    &cat.full_name(db),
+                    Self::is_income_budget(cat, allotment),
+                    sum,
+                    allotment,
+                    carried,
+                    converted,
+                )
             })
-            // filter out categories that don't have a budget
-            .filter(|&cat| cat.has_budget())
-            .cloned()
             .collect();
 
-        filt_categories.sort_by_key(|a| a.full_name(db));
+        budget_spent
+    }
+}
 
-        let budget_spent: Vec<BudgetSummary> = filt_categories
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monthly_budget_matrix() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b1="-100" b2="-100"/>
+<cat key="2" name="Unbudgeted"/>
+<ope date="737791" amount="-120" account="1" category="1"/>
+<ope date="737830" amount="-50" account="1" category="1"/>
+<ope date="737830" amount="-10" account="1" category="2"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(),
+            None,
+            true,
+            Some(2021),
+            false,
+            None,
+            false,
+            None,
+            3,
+        );
+        let observed = query.exec_matrix(&db);
+
+        assert_eq!(1, observed.len());
+        assert_eq!("Food", observed[0].name());
+
+        let months = observed[0].months();
+        assert_eq!(12, months.len());
+        assert_eq!(
+            BudgetMonth::new(Money::from(-100), Money::from(-120), Money::from(-20)),
+            months[0]
+        );
+        assert!(months[0].is_over_budget());
+        assert_eq!(
+            BudgetMonth::new(Money::from(-100), Money::from(-50), Money::from(30)),
+            months[1]
+        );
+        assert!(!months[1].is_over_budget());
+        assert_eq!(
+            BudgetMonth::new(Money::ZERO, Money::ZERO, Money::from(30)),
+            months[2]
+        );
+    }
+
+    #[test]
+    fn rollover_budget() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b0="-100"/>
+<ope date="737791" amount="-80" account="1" category="1"/>
+<ope date="737830" amount="-130" account="1" category="1"/>
+<ope date="737850" amount="-45" account="1" category="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(),
+            None,
+            false,
+            None,
+            true,
+            None,
+            false,
+            None,
+            3,
+        );
+        let observed = query.exec(&db);
+
+        assert_eq!(1, observed.len());
+        assert_eq!(Money::from(-45), observed[0].progress());
+        assert_eq!(Some(Money::from(-100)), observed[0].allotment());
+        // 20 left over in January, and 30 overspent in February
+        assert_eq!(Money::from(10), observed[0].carried());
+        assert_eq!(Some(Money::from(-90)), observed[0].available());
+        assert_eq!(&Some(0.5), observed[0].progress_frac());
+    }
+
+    #[test]
+    fn rollover_overspending_past_allotment() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b0="-100"/>
+<ope date="737791" amount="-300" account="1" category="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            None,
+            false,
+            None,
+            true,
+            None,
+            false,
+            None,
+            3,
+        );
+        let observed = query.exec(&db);
+
+        // 200 overspent in January is more than February's allotment
+        assert_eq!(Some(Money::from(100)), observed[0].available());
+        assert_eq!(Money::ZERO, observed[0].progress());
+        assert!(observed[0].is_over_budget());
+    }
+
+    #[test]
+    fn rollover_nothing_available() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b0="-100"/>
+<cat key="2" name="Fun" b0="-100"/>
+<ope date="737791" amount="-200" account="1" category="1"/>
+<ope date="737791" amount="-200" account="1" category="2"/>
+<ope date="737830" amount="-10" account="1" category="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            None,
+            false,
+            None,
+            true,
+            None,
+            false,
+            None,
+            3,
+        );
+        let summaries = query.exec(&db);
+        let observed: Vec<(&str, Option<Money>, Option<f32>, bool)> = summaries
             .iter()
-            .map(|cat| {
-                let cat_name_re = Regex::new(&cat.full_name(db)).unwrap();
-                let transaction_query = QueryTransactions::new(
-                    &Some(*self.date_from()),
-                    &Some(*self.date_to()),
-                    &None,
-                    &None,
-                    &None,
-                    &Some(cat_name_re),
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                );
-
-                let filt_transactions = transaction_query.exec(db);
-                let sum = sum_transactions(&filt_transactions);
-                let allotment = cat.budget_amount_over_interval(*self.date_from(), *self.date_to());
-
-                BudgetSummary::new(&cat.full_name(db), sum, allotment)
+            .map(|summary| {
+                (
+                    summary.name(),
+                    summary.available(),
+                    *summary.progress_frac(),
+                    summary.is_over_budget(),
+                )
             })
             .collect();
+        let expected = vec![
+            // any spending is over when January used up February's allotment
+            ("Food", Some(Money::ZERO), None, true),
+            ("Fun", Some(Money::ZERO), None, false),
+        ];
 
-        budget_spent
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn project_to_end_of_month() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b0="-200"/>
+<cat key="2" name="Fun" b0="-50"/>
+<fav key="1" amount="-15" account="1" category="1" flags="4" wording="Lunch" nextdate="737869" every="1" unit="2"/>
+<ope date="737826" amount="-999" account="1" category="1"/>
+<ope date="737841" amount="-60" account="1" category="1"/>
+<ope date="737854" amount="-100" account="1" category="1"/>
+<ope date="737854" amount="-60" account="1" category="2"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(),
+            None,
+            false,
+            None,
+            false,
+            None,
+            true,
+            NaiveDate::from_ymd_opt(2021, 3, 10),
+            1,
+        );
+        let observed = query.exec_projection(&db);
+
+        // 21 more days at the pace of the first 10, averaged with the rest of February,
+        // plus the scheduled lunch
+        assert_eq!(
+            BudgetProjection::new(
+                "Food",
+                false,
+                Some(Money::from(-200)),
+                Money::from(-100),
+                Money::from(-15),
+                Money::from(-250),
+                false,
+            ),
+            observed[0]
+        );
+        assert!(!observed[0].is_over());
+        assert!(observed[0].is_projected_over());
+        assert!(observed[1].is_over());
+    }
+
+    #[test]
+    fn project_scheduled_like_spending() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<account key="2" flags="32" name="Business"/>
+<cat key="1" name="Food" b0="-200"/>
+<cat key="2" parent="1" flags="1" name="Dining"/>
+<fav key="1" amount="-15" account="1" category="2" flags="4" wording="Lunch" nextdate="737869" every="1" unit="2"/>
+<fav key="2" amount="-40" account="2" category="1" flags="4" wording="Client dinner" nextdate="737869" every="1" unit="2"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(),
+            None,
+            false,
+            None,
+            false,
+            None,
+            true,
+            NaiveDate::from_ymd_opt(2021, 3, 10),
+            1,
+        );
+        let observed = query.exec_projection(&db);
+
+        // the lunch in a subcategory counts, but not the dinner in an account left out of budgets
+        assert_eq!(Money::from(-15), observed[0].scheduled());
+        assert_eq!(Money::from(-15), observed[0].projected());
+    }
+
+    #[test]
+    fn income_budgets_are_targets() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b0="-100"/>
+<cat key="2" flags="2" name="Salary" b0="3000"/>
+<cat key="3" name="Tips" b0="200"/>
+<ope date="737791" amount="-110" account="1" category="1"/>
+<ope date="737791" amount="3300" account="1" category="2"/>
+<ope date="737791" amount="50" account="1" category="3"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(),
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None,
+            3,
+        );
+        let summaries = query.exec(&db);
+        let observed: Vec<(&str, bool, bool, bool)> = summaries
+            .iter()
+            .map(|summary| {
+                (
+                    summary.name(),
+                    summary.is_income(),
+                    summary.is_over_budget(),
+                    summary.is_under_target(),
+                )
+            })
+            .collect();
+        let expected = vec![
+            ("Food", false, true, false),
+            // going past an income target isn't overspending
+            ("Salary", true, false, false),
+            // a positive budget is a target, even without the income flag
+            ("Tips", true, false, true),
+        ];
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn spending_by_category_key() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food" b0="-100"/>
+<cat key="2" parent="1" flags="1" name="Dining"/>
+<cat key="3" name="Seafood"/>
+<cat key="4" name="Groceries (bulk" b0="-50"/>
+<ope date="737791" amount="-10" account="1" category="1"/>
+<ope date="737791" amount="-20" account="1" category="2"/>
+<ope date="737791" amount="-40" account="1" flags="256" scat="3||4" samt="-30||-10" smem="||"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryBudget::new(
+            None,
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(),
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None,
+            3,
+        );
+        let observed: Vec<(String, Money)> = query
+            .exec(&db)
+            .iter()
+            .map(|summary| (summary.name().to_string(), summary.progress()))
+            .collect();
+        let expected = vec![
+            // subcategories count towards their parent, but similar names don't
+            ("Food".to_string(), Money::from(-30)),
+            ("Groceries (bulk".to_string(), Money::from(-10)),
+        ];
+
+        assert_eq!(expected, observed);
     }
 }
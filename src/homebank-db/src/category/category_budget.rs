@@ -1,6 +1,6 @@
 //! A budget for a given [`Category`][crate::category::category_struct::Category].
 
-use crate::CategoryError;
+use crate::{CategoryError, Money};
 use chrono::{Datelike, Duration, NaiveDate};
 use kronos::{Grain, Grains, NthOf, TimeSequence};
 
@@ -8,20 +8,20 @@ use kronos::{Grain, Grains, NthOf, TimeSequence};
 #[derive(Debug, PartialEq, Clone)]
 pub struct CategoryBudget {
     /// A uniform budget for each month.
-    pub each_month: Option<f32>,
+    pub each_month: Option<Money>,
     
-    pub january: Option<f32>,
-    pub february: Option<f32>,
-    pub march: Option<f32>,
-    pub april: Option<f32>,
-    pub may: Option<f32>,
-    pub june: Option<f32>,
-    pub july: Option<f32>,
-    pub august: Option<f32>,
-    pub september: Option<f32>,
-    pub october: Option<f32>,
-    pub november: Option<f32>,
-    pub december: Option<f32>,
+    pub january: Option<Money>,
+    pub february: Option<Money>,
+    pub march: Option<Money>,
+    pub april: Option<Money>,
+    pub may: Option<Money>,
+    pub june: Option<Money>,
+    pub july: Option<Money>,
+    pub august: Option<Money>,
+    pub september: Option<Money>,
+    pub october: Option<Money>,
+    pub november: Option<Money>,
+    pub december: Option<Money>,
 }
 
 impl CategoryBudget {
@@ -56,7 +56,7 @@ impl CategoryBudget {
     }
 
     /// Set the budget amount for a month or each month
-    pub fn set_budget(&mut self, index: usize, amount: f32) -> Result<(), CategoryError> {
+    pub fn set_budget(&mut self, index: usize, amount: Money) -> Result<(), CategoryError> {
         match index {
             0 => self.each_month = Some(amount),
             1 => self.january = Some(amount),
@@ -77,10 +77,32 @@ impl CategoryBudget {
         Ok(())
     }
 
+    /// Get the budget amount exactly as it is stored for a month (`1` - `12`) or each month (`0`).
+    ///
+    /// Unlike [`budget`][CategoryBudget::budget], this does not apply the `each_month` budget to individual months.
+    pub fn raw_budget(&self, index: usize) -> Option<Money> {
+        match index {
+            0 => self.each_month,
+            1 => self.january,
+            2 => self.february,
+            3 => self.march,
+            4 => self.april,
+            5 => self.may,
+            6 => self.june,
+            7 => self.july,
+            8 => self.august,
+            9 => self.september,
+            10 => self.october,
+            11 => self.november,
+            12 => self.december,
+            _ => None,
+        }
+    }
+
     /// Get the budget amount for the given month.
     ///
     /// Returns `None` for a month index that is not within 1 - 12 (inclusive).
-    pub fn budget(&self, month: usize) -> Option<f32> {
+    pub fn budget(&self, month: usize) -> Option<Money> {
         if month == 0 || month > 12 {
             return None;
         }
@@ -111,7 +133,7 @@ impl CategoryBudget {
     ///
     /// The dates form a half-open interval [`from`, `to`) including the first date and excluding the second.
     /// Only the months for the `from` and `to` dates are considered, since budgets are set per month.
-    pub fn budget_over_interval(&self, from: NaiveDate, to: NaiveDate) -> Option<f32> {
+    pub fn budget_over_interval(&self, from: NaiveDate, to: NaiveDate) -> Option<Money> {
         if self.is_empty() {
             return None;
         }
@@ -140,13 +162,13 @@ impl CategoryBudget {
         );
         let mut date_iter = time_step.next().unwrap().start.date();
 
-        let mut sum = 0.0;
+        let mut sum = Money::ZERO;
 
         // iterate over all the first days of each month
         while date_iter < last_date {
             date_iter = time_step.next().unwrap().start.date();
 
-            sum += self.budget(date_iter.month() as usize).unwrap_or(0.0);
+            sum += self.budget(date_iter.month() as usize).unwrap_or(Money::ZERO);
         }
 
         Some(sum)
@@ -170,7 +192,7 @@ mod tests {
     }
 
     #[track_caller]
-    fn check_budget_amount(input: (&CategoryBudget, usize), expected: Option<f32>) {
+    fn check_budget_amount(input: (&CategoryBudget, usize), expected: Option<Money>) {
         let budget = input.0;
         let month = input.1;
         let observed = budget.budget(month);
@@ -181,10 +203,10 @@ mod tests {
     #[test]
     fn each_month_overrides_none() {
         let budget = CategoryBudget {
-            each_month: Some(100.0),
+            each_month: Some(Money::from(100)),
             ..Default::default()
         };
-        let expected = Some(100.0);
+        let expected = Some(Money::from(100));
 
         // check that it's equal for all allowable months
         for i in 1..=12 {
@@ -195,7 +217,7 @@ mod tests {
     #[test]
     fn outside_1_12_is_none() {
         let budget = CategoryBudget {
-            each_month: Some(100.0),
+            each_month: Some(Money::from(100)),
             ..Default::default()
         };
 
@@ -206,21 +228,21 @@ mod tests {
     #[test]
     fn each_month_overrides_any_month() {
         let budget = CategoryBudget {
-            each_month: Some(100.0),
-            january: Some(1.0),
-            february: Some(2.0),
-            march: Some(3.0),
-            april: Some(4.0),
-            may: Some(5.0),
-            june: Some(6.0),
-            july: Some(7.0),
-            august: Some(8.0),
-            september: Some(9.0),
-            october: Some(10.0),
-            november: Some(11.0),
-            december: Some(12.0),
+            each_month: Some(Money::from(100)),
+            january: Some(Money::from(1)),
+            february: Some(Money::from(2)),
+            march: Some(Money::from(3)),
+            april: Some(Money::from(4)),
+            may: Some(Money::from(5)),
+            june: Some(Money::from(6)),
+            july: Some(Money::from(7)),
+            august: Some(Money::from(8)),
+            september: Some(Money::from(9)),
+            october: Some(Money::from(10)),
+            november: Some(Money::from(11)),
+            december: Some(Money::from(12)),
         };
-        let expected = Some(100.0);
+        let expected = Some(Money::from(100));
 
         // check that it's equal for all allowable months
         for i in 1..=12 {
@@ -232,23 +254,23 @@ mod tests {
     fn single_month() {
         let budget = CategoryBudget {
             each_month: None,
-            january: Some(1.0),
-            february: Some(2.0),
-            march: Some(3.0),
-            april: Some(4.0),
-            may: Some(5.0),
-            june: Some(6.0),
-            july: Some(7.0),
-            august: Some(8.0),
-            september: Some(9.0),
-            october: Some(10.0),
-            november: Some(11.0),
-            december: Some(12.0),
+            january: Some(Money::from(1)),
+            february: Some(Money::from(2)),
+            march: Some(Money::from(3)),
+            april: Some(Money::from(4)),
+            may: Some(Money::from(5)),
+            june: Some(Money::from(6)),
+            july: Some(Money::from(7)),
+            august: Some(Money::from(8)),
+            september: Some(Money::from(9)),
+            october: Some(Money::from(10)),
+            november: Some(Money::from(11)),
+            december: Some(Money::from(12)),
         };
 
         // check that it's equal for all allowable months
         for i in 1..=12 {
-            check_budget_amount((&budget, i), Some(i as f32));
+            check_budget_amount((&budget, i), Some(Money::from(i as i64)));
         }
     }
 
@@ -263,7 +285,7 @@ mod tests {
     #[test]
     fn check_some_budget_each_month() {
         let budget = CategoryBudget {
-            each_month: Some(1.0),
+            each_month: Some(Money::from(1)),
             ..Default::default()
         };
         let observed = budget.is_empty();
@@ -274,7 +296,7 @@ mod tests {
     #[test]
     fn check_some_budget_one_month() {
         let budget = CategoryBudget {
-            january: Some(1.0),
+            january: Some(Money::from(1)),
             ..Default::default()
         };
         let observed = budget.is_empty();
@@ -21,7 +21,7 @@ pub enum CategoryError {
     #[error("Invalid category budget property.")]
     InvalidBudgetProperty,
     
-    /// When the budget for some month, or all months, cannot be properly parsed as an `f32`.
+    /// When the budget for some month, or all months, cannot be properly parsed as an amount of [`Money`][crate::money::money_struct::Money].
     #[error("Invalid category budget value.")]
     InvalidBudgetValue,
     
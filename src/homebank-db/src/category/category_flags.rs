@@ -0,0 +1,50 @@
+//! Flags set on a [`Category`][crate::category::category_struct::Category].
+
+use crate::flags::flag_set;
+
+flag_set! {
+    /// Flags set on a [`Category`][crate::category::category_struct::Category].
+    pub struct CategoryFlags {
+        /// The category is a subcategory of another one (`GF_SUB`).
+        const SUB = 1 << 0;
+
+        /// The category is for income rather than expenses (`GF_INCOME`).
+        const INCOME = 1 << 1;
+
+        /// The category has a different budget for some months (`GF_CUSTOM`).
+        const CUSTOM = 1 << 2;
+
+        /// The category has a budget (`GF_BUDGET`).
+        const BUDGET = 1 << 3;
+
+        /// The category is shown in budgets even without a budget amount (`GF_FORCED`).
+        const FORCED = 1 << 4;
+
+        /// The category is hidden from lists (`GF_HIDDEN`).
+        const HIDDEN = 1 << 6;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_bits_are_kept() {
+        let flags = CategoryFlags::from(0b1010_0010);
+
+        assert!(flags.contains(CategoryFlags::INCOME));
+        assert!(!flags.contains(CategoryFlags::SUB));
+        assert_eq!("162", flags.to_string());
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut flags = CategoryFlags::empty();
+        flags.insert(CategoryFlags::INCOME | CategoryFlags::BUDGET);
+        assert_eq!(10, flags.bits());
+
+        flags.remove(CategoryFlags::INCOME);
+        assert_eq!(CategoryFlags::BUDGET, flags);
+    }
+}
@@ -16,6 +16,14 @@ pub struct QueryCategories {
     /// Name of the [`Category`][crate::category::category_struct::Category]
     #[clap(value_name = "regex")]
     name: Option<Regex>,
+
+    /// Only include income categories.
+    #[clap(short = 'I', long = "income", conflicts_with = "expense")]
+    income: bool,
+
+    /// Only include expense categories.
+    #[clap(short = 'E', long = "expense")]
+    expense: bool,
 }
 
 impl QueryCategories {
@@ -23,6 +31,16 @@ impl QueryCategories {
     fn name(&self) -> &Option<Regex> {
         &self.name
     }
+
+    /// Retrieve whether only income categories are included
+    fn income(&self) -> bool {
+        self.income
+    }
+
+    /// Retrieve whether only expense categories are included
+    fn expense(&self) -> bool {
+        self.expense
+    }
 }
 
 impl Query for QueryCategories {
@@ -37,6 +55,12 @@ impl Query for QueryCategories {
                 Some(re) => re.is_match(&p.full_name(db)),
                 None => true,
             })
+            // filter income or expense categories
+            .filter(|&p| match (self.income(), self.expense()) {
+                (true, _) => p.is_income(),
+                (_, true) => !p.is_income(),
+                _ => true,
+            })
             .cloned()
             .collect();
 
@@ -45,3 +69,49 @@ impl Query for QueryCategories {
         filt_categories
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_exec(query: QueryCategories, expected: Vec<&str>) {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<cat key="1" flags="0" name="Food"/>
+<cat key="2" flags="2" name="Salary"/>
+<cat key="3" parent="1" flags="1" name="Groceries"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let observed: Vec<String> = query
+            .exec(&db)
+            .iter()
+            .map(|cat| cat.full_name(&db))
+            .collect();
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn income_categories() {
+        let query = QueryCategories {
+            name: None,
+            income: true,
+            expense: false,
+        };
+
+        check_exec(query, vec!["Salary"]);
+    }
+
+    #[test]
+    fn expense_categories() {
+        let query = QueryCategories {
+            name: None,
+            income: false,
+            expense: true,
+        };
+
+        check_exec(query, vec!["Food", "Food:Groceries"]);
+    }
+}
@@ -1,7 +1,7 @@
 //! Categories for each [`Transaction`][crate::transaction::transaction_struct::Transaction].
 
-use super::{CategoryBudget, CategoryError};
-use crate::HomeBankDb;
+use super::{CategoryBudget, CategoryError, CategoryFlags};
+use crate::{db::db_writer::xml_attribute, HomeBankDb, Money};
 use chrono::NaiveDate;
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
@@ -14,61 +14,8 @@ pub struct Category {
 
     /// Flags listed on the category.
     ///
-    /// The following flags are found in the original codebase:
-    /// - `AF_ADDED`
-    /// - `AF_CHANGED`
-    /// - `AF_CLOSED`
-    /// - `AF_NOBUDGET`
-    /// - `AF_NOREPORT`
-    /// - `AF_NOSUMMARY`
-    /// - `AF_OLDBUDGET`
-    /// - `ASGF_DOCAT`
-    /// - `ASGF_DOMOD`
-    /// - `ASGF_DOPAY`
-    /// - `ASGF_EXACT`
-    /// - `ASGF_OVWCAT`
-    /// - `ASGF_OVWMOD`
-    /// - `ASGF_OVWPAY`
-    /// - `ASGF_REGEX`
-    /// - `CF_CUSTOM`
-    /// - `FLG_REG_TITLE`
-    /// - `FLG_REG_VISUAL`
-    /// - `FLG_REG_BALANCE`
-    /// - `FLG_REG_SENSITIVE`
-    /// - `FLG_REG_`
-    /// - `FLT_QSEARCH_MEMO`
-    /// - `FLT_QSEARCH_INFO`
-    /// - `FLT_QSEARCH_PAYEE`
-    /// - `FLT_QSEARCH_CATEGORY`
-    /// - `FLT_QSEARCH_TAGS`
-    /// - `FLT_QSEARCH_AMOUNT`
-    /// - `GF_BUDGET`
-    /// - `GF_CUSTOM`
-    /// - `GF_FORCED`
-    /// - `GF_INCOME`
-    /// - `GF_MIXED`
-    /// - `GF_SUB`
-    /// - `OF_ADDED`
-    /// - `OF_AUTO`
-    /// - `OF_CHANGED`
-    /// - `OF_CHEQ2`
-    /// - `OF_INCOME`
-    /// - `OF_INTXFER`
-    /// - `OF_LIMIT`
-    /// - `OF_REMIND`
-    /// - `OF_SPLIT`
-    /// - `OF_VALID`
-    /// - `OLDF_REMIND`
-    /// - `OLDF_VALID`
-    /// - `TXN_DSPFLG_DUPDST`
-    /// - `TXN_DSPFLG_DUPSRC`
-    /// - `TXN_DSPFLG_OVER`
-    /// - `TXN_DSPFLG_LOWBAL`
-    /// - `UF_TITLE`
-    /// - `UF_SENSITIVE`
-    /// - `UF_VISUAL`
-    /// - `UF_REFRESHALL`
-    flags: usize,
+    /// See [`CategoryFlags`] for the flags that are understood.
+    flags: CategoryFlags,
 
     /// The name of the category.
     name: String,
@@ -112,7 +59,7 @@ impl Category {
     pub fn empty() -> Self {
         Self {
             key: 0,
-            flags: 0,
+            flags: CategoryFlags::empty(),
             name: "".to_string(),
             budget: CategoryBudget::default(),
             parent_key: None,
@@ -120,7 +67,7 @@ impl Category {
     }
 
     /// Create a new `Category`
-    pub fn new(key: usize, flags: usize, name: &str, parent_key: Option<usize>) -> Self {
+    pub fn new(key: usize, flags: CategoryFlags, name: &str, parent_key: Option<usize>) -> Self {
         Self {
             key,
             flags,
@@ -135,6 +82,11 @@ impl Category {
         self.key
     }
 
+    /// Retrieve the key of the `Category`'s parent category, if one exists.
+    pub(crate) fn parent_key(&self) -> Option<usize> {
+        self.parent_key
+    }
+
     /// Retrieve the `Category`'s name
     pub fn name(&self) -> &str {
         &self.name
@@ -172,12 +124,22 @@ impl Category {
     }
 
     /// Retrieve the [`Category`][crate::category::category_struct::Category]'s flags.
-    pub fn flags(&self) -> usize {
+    pub fn flags(&self) -> CategoryFlags {
         self.flags
     }
 
+    /// Check if the `Category` is for income rather than expenses.
+    pub fn is_income(&self) -> bool {
+        self.flags.contains(CategoryFlags::INCOME)
+    }
+
+    /// Check if the `Category` is hidden from lists.
+    pub fn is_hidden(&self) -> bool {
+        self.flags.contains(CategoryFlags::HIDDEN)
+    }
+
     /// Set the budget amount for a month or each month.
-    pub fn set_budget(&mut self, index: usize, amount: f32) -> Result<(), CategoryError> {
+    pub fn set_budget(&mut self, index: usize, amount: Money) -> Result<(), CategoryError> {
         self.budget.set_budget(index, amount)
     }
 
@@ -192,12 +154,12 @@ impl Category {
     }
 
     /// Retrieve the budget amount for a given month.
-    pub fn budget_amount(&self, month: usize) -> Option<f32> {
+    pub fn budget_amount(&self, month: usize) -> Option<Money> {
         self.budget.budget(month)
     }
 
     /// Retrieve the total budget amount of an interval of time.
-    pub fn budget_amount_over_interval(&self, from: NaiveDate, to: NaiveDate) -> Option<f32> {
+    pub fn budget_amount_over_interval(&self, from: NaiveDate, to: NaiveDate) -> Option<Money> {
         self.budget.budget_over_interval(from, to)
     }
 }
@@ -230,7 +192,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Category {
                 // flags for the category
                 "flags" => {
                     cat.flags = match usize::from_str(&i.value) {
-                        Ok(idx) => idx,
+                        Ok(f) => CategoryFlags::from(f),
                         Err(_) => return Err(CategoryError::InvalidFlags),
                     }
                 }
@@ -248,7 +210,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Category {
                         Ok(i) => i,
                         Err(_) => return Err(CategoryError::InvalidBudgetProperty),
                     };
-                    let amount = match f32::from_str(&i.value) {
+                    let amount = match Money::from_str(&i.value) {
                         Ok(v) => v,
                         Err(_) => return Err(CategoryError::InvalidBudgetValue),
                     };
@@ -261,6 +223,30 @@ impl TryFrom<Vec<OwnedAttribute>> for Category {
     }
 }
 
+impl From<&Category> for Vec<OwnedAttribute> {
+    fn from(cat: &Category) -> Self {
+        let mut v = vec![xml_attribute("key", cat.key)];
+
+        if let Some(parent) = cat.parent_key {
+            v.push(xml_attribute("parent", parent));
+        }
+        // HomeBank leaves out flags of 0
+        if cat.flags.bits() != 0 {
+            v.push(xml_attribute("flags", cat.flags));
+        }
+        v.push(xml_attribute("name", &cat.name));
+
+        // budgets are stored as `b0` (each month) through `b12` (December)
+        for i in 0..=12 {
+            if let Some(amount) = cat.budget.raw_budget(i) {
+                v.push(xml_attribute(&format!("b{i}"), amount));
+            }
+        }
+
+        v
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,9 +317,9 @@ mod tests {
             key: 1,
             name: "Name".to_string(),
             parent_key: None,
-            flags: 0,
+            flags: CategoryFlags::empty(),
             budget: CategoryBudget {
-                each_month: Some(-400.0),
+                each_month: Some(Money::from(-400)),
                 ..Default::default()
             },
         });
@@ -348,10 +334,10 @@ mod tests {
             key: 1,
             name: "Name".to_string(),
             parent_key: None,
-            flags: 0,
+            flags: CategoryFlags::empty(),
             budget: CategoryBudget {
-                each_month: Some(-400.0),
-                february: Some(-200.0),
+                each_month: Some(Money::from(-400)),
+                february: Some(Money::from(-200)),
                 ..Default::default()
             },
         });
@@ -366,9 +352,9 @@ mod tests {
             key: 1,
             name: "Name".to_string(),
             parent_key: None,
-            flags: 0,
+            flags: CategoryFlags::empty(),
             budget: CategoryBudget {
-                february: Some(-200.0),
+                february: Some(Money::from(-200)),
                 ..Default::default()
             },
         });
@@ -376,12 +362,27 @@ mod tests {
         check_try_from_single_str(input, expected);
     }
 
+    #[test]
+    fn parse_income_category() {
+        let input = r#"<cat key="3" flags="2" name="Salary">"#;
+        let observed = Category {
+            key: 3,
+            flags: CategoryFlags::INCOME,
+            name: "Salary".to_string(),
+            ..Default::default()
+        };
+
+        check_try_from_single_str(input, Ok(observed.clone()));
+        assert!(observed.is_income());
+        assert!(!observed.is_hidden());
+    }
+
     #[test]
     fn parse_non_budget() {
         let cat = Category {
             key: 157,
             parent_key: Some(106),
-            flags: 1,
+            flags: CategoryFlags::SUB,
             name: "Parking".to_string(),
             budget: CategoryBudget::empty(),
         };
@@ -394,10 +395,10 @@ mod tests {
         let cat = Category {
             key: 157,
             parent_key: Some(106),
-            flags: 1,
+            flags: CategoryFlags::SUB,
             name: "Parking".to_string(),
             budget: CategoryBudget {
-                february: Some(2.0),
+                february: Some(Money::from(2)),
                 ..Default::default()
             },
         };
@@ -4,15 +4,19 @@ pub mod budget_query;
 pub mod category_struct;
 pub mod category_budget;
 pub mod category_error;
+pub mod category_flags;
 pub mod category_query;
 pub mod review_query;
+pub mod trend_query;
 
 pub use budget_query::QueryBudget;
 pub use category_struct::Category;
 pub use category_budget::CategoryBudget;
 pub use category_error::CategoryError;
+pub use category_flags::CategoryFlags;
 pub use category_query::QueryCategories;
 pub use review_query::QueryReview;
+pub use trend_query::QueryTrend;
 
 use chrono::{Datelike, Local, NaiveDate};
 use kronos::{Grain, Grains, NthOf, TimeSequence};
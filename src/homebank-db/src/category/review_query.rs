@@ -1,12 +1,15 @@
 //! Review the sums across each (sub)category in your HomeBank database.
 
-use crate::{transaction::sum_transactions, HomeBankDb, Query, QueryTransactions};
-use super::{TODAY_FIRST_OF_MONTH_STR, FIRST_OF_NEXT_MONTH_STR};
+use crate::{
+    interval::{parse_date, parse_date_to},
+    transaction::sum_transactions_in, DateExpr, HomeBankDb, Money, Query,
+    QueryTransactions, Transaction,
+};
+use super::{Category, TODAY_FIRST_OF_MONTH_STR, FIRST_OF_NEXT_MONTH_STR};
 
 use chrono::NaiveDate;
 use clap::Parser;
-use regex::Regex;
-use std::str::FromStr;
+use std::collections::HashMap;
 
 
 /// Query the budget in your HomeBank database.
@@ -17,7 +20,8 @@ pub struct QueryReview {
         short = 'd',
         long = "date-from",
         default_value = &TODAY_FIRST_OF_MONTH_STR,
-        parse(try_from_str = NaiveDate::from_str),
+        parse(try_from_str = parse_date),
+        allow_hyphen_values = true,
         value_name = "date"
     )]
     date_from: NaiveDate,
@@ -27,80 +31,251 @@ pub struct QueryReview {
         short = 'D',
         long = "date-to",
         default_value = &FIRST_OF_NEXT_MONTH_STR,
-        parse(try_from_str = NaiveDate::from_str),
+        parse(try_from_str = parse_date_to),
+        allow_hyphen_values = true,
         value_name = "date"
     )]
     date_to: NaiveDate,
 
+    /// Consider the transactions in this period, instead of `--date-from` and `--date-to`.
+    /// Use a period like '2026-Q2', '2026-03', 'last-month', or 'ytd', or a relative date like '-30d' for the days since then.
+    #[clap(
+        long = "period",
+        conflicts_with_all = &["date-from", "date-to"],
+        allow_hyphen_values = true,
+        value_name = "period"
+    )]
+    period: Option<DateExpr>,
+
     /// Exclude any (sub)categories that have no transactions.
     #[clap(short = 'x')]
-    exclude_none: bool
+    exclude_none: bool,
+
+    /// Convert the sums into the currency with this ISO code, instead of the base currency.
+    #[clap(long = "currency", value_name = "ISO")]
+    currency: Option<String>,
+
+    /// Roll up the sums of subcategories into their parent categories, with a grand total.
+    #[clap(short = 'R', long = "rollup")]
+    rollup: bool,
 }
 
 impl QueryReview {
     /// Create a new query for budgets
-    pub fn new(date_from: NaiveDate, date_to: NaiveDate, exclude_none: bool) -> Self {
+    pub fn new(
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+        exclude_none: bool,
+        currency: Option<String>,
+        rollup: bool,
+    ) -> Self {
         Self {
             date_from,
             date_to,
+            period: None,
             exclude_none,
+            currency,
+            rollup,
         }
     }
 
     /// Retrieve the earliest date that the budget is including
-    fn date_from(&self) -> &NaiveDate {
-        &self.date_from
+    fn date_from(&self) -> NaiveDate {
+        self.period.map_or(self.date_from, |period| period.start())
     }
 
     /// Retrieve the latest date that the budget is including
-    fn date_to(&self) -> &NaiveDate {
-        &self.date_to
+    fn date_to(&self) -> NaiveDate {
+        self.period.map_or(self.date_to, |period| period.end())
     }
 
-    /// Retrieve whether some 
+    /// Retrieve whether (sub)categories without any transactions are left out
     fn excluded_none(&self) -> bool {
         self.exclude_none
     }
+
+    /// Retrieve the ISO code of the currency to convert the sums into, if not the base currency.
+    ///
+    /// Nothing is returned by the query if the currency is missing, or if amounts can't be converted into it, so check it first with [`HomeBankDb::currency_key`].
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+
+    /// Retrieve whether subcategories are rolled up into their parent categories
+    pub fn rollup(&self) -> bool {
+        self.rollup
+    }
+
+    /// Retrieve the transactions assigned directly to a category, leaving out its subcategories
+    fn category_transactions(&self, cat: &Category, db: &HomeBankDb) -> Vec<Transaction> {
+        // get all the transactions in the date range
+        let transaction_query = QueryTransactions::new(
+            &Some(self.date_from()),
+            &Some(self.date_to()),
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+        );
+
+        transaction_query
+            .exec(db)
+            .iter()
+            // accounts can be left out of reports, like in HomeBank
+            .filter(|tr| !tr.is_excluded_from_reports(db))
+            // keep only the parts assigned to the category (by key, to exclude subcategories)
+            .filter_map(|tr| tr.subset_categories(&[cat.key()]))
+            .collect()
+    }
+
+    /// Sum each category, including the transactions of all its subcategories.
+    ///
+    /// Categories are listed depth-first, with each parent before its subcategories.
+    pub fn exec_rollup(&self, db: &HomeBankDb) -> Vec<CategoryRollup> {
+        // totals can't be made in a currency that is missing or that amounts can't be converted into
+        let currency = match db.currency_key(self.currency()) {
+            Ok(key) => key,
+            Err(_) => return vec![],
+        };
+
+        // the subcategories of each category, with `None` for the top-level categories
+        let mut children: HashMap<Option<usize>, Vec<&Category>> = HashMap::new();
+        for cat in db.categories().values() {
+            // categories whose parent is missing are treated as top-level categories
+            let parent = cat
+                .parent_key()
+                .filter(|idx| db.categories().contains_key(idx));
+            children.entry(parent).or_default().push(cat);
+        }
+        for cats in children.values_mut() {
+            cats.sort_by(|a, b| a.name().cmp(b.name()));
+        }
+
+        let mut rollups = vec![];
+        self.rollup_subtree(None, 0, &children, &mut rollups, db, currency);
+
+        rollups
+    }
+
+    /// Add the rollups for the subcategories of a category, returning all of their transactions.
+    fn rollup_subtree(
+        &self,
+        parent: Option<usize>,
+        depth: usize,
+        children: &HashMap<Option<usize>, Vec<&Category>>,
+        rollups: &mut Vec<CategoryRollup>,
+        db: &HomeBankDb,
+        currency: usize,
+    ) -> Vec<Transaction> {
+        let mut subtree_transactions = vec![];
+
+        for cat in children.get(&parent).into_iter().flatten() {
+            // reserve the spot for the category before its subcategories
+            let idx = rollups.len();
+            rollups.push(CategoryRollup::new(cat.name(), depth, Money::ZERO, false));
+
+            let mut transactions = self.category_transactions(cat, db);
+            transactions.extend(self.rollup_subtree(
+                Some(cat.key()),
+                depth + 1,
+                children,
+                rollups,
+                db,
+                currency,
+            ));
+
+            if transactions.is_empty() && self.excluded_none() {
+                rollups.truncate(idx);
+                continue;
+            }
+
+            let (sum, converted) = sum_transactions_in(&transactions, db, currency);
+            rollups[idx] = CategoryRollup::new(cat.name(), depth, sum, converted);
+            subtree_transactions.extend(transactions);
+        }
+
+        subtree_transactions
+    }
+}
+
+/// The sum of a category and all of its subcategories.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CategoryRollup {
+    /// Name of the category, without its parent categories.
+    name: String,
+
+    /// How many parent categories the category has.
+    depth: usize,
+
+    /// Sum of the category and all of its subcategories.
+    sum: Money,
+
+    /// Whether any of the sum was converted from another currency.
+    converted: bool,
+}
+
+impl CategoryRollup {
+    /// Create a new rollup for a category
+    pub fn new(name: &str, depth: usize, sum: Money, converted: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            depth,
+            sum,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the category, without its parent categories
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieve how many parent categories the category has
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Retrieve the sum of the category and all of its subcategories
+    pub fn sum(&self) -> Money {
+        self.sum
+    }
+
+    /// Check if any of the sum was converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
 }
 
 impl Query for QueryReview {
-    type T = (String, Option<String>, f32);
+    /// The category name, subcategory name, sum, and whether any of the sum was converted from another currency.
+    type T = (String, Option<String>, Money, bool);
 
     fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
-        let mut vals: Vec<(String, Option<String>, usize, f32)> = db.categories()
+        // totals can't be made in a currency that is missing or that amounts can't be converted into
+        let currency = match db.currency_key(self.currency()) {
+            Ok(key) => key,
+            Err(_) => return vec![],
+        };
+        let mut vals: Vec<(String, Option<String>, usize, Money, bool)> = db.categories()
             .values()
             .map(|cat| {
-                // create a regex from the category name (match the name exactly to exclude subcategories)
-                let re_str = format!("^{}$", &cat.full_name(db));
-                let re = Regex::from_str(&re_str).unwrap();
-
-                // get all the transactions for that category
-                let transaction_query = QueryTransactions::new(
-                    &Some(*self.date_from()),
-                    &Some(*self.date_to()),
-                    &None,
-                    &None,
-                    &None,
-                    &Some(re),
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                    &None,
-                );
-
-                let filt_transactions = transaction_query.exec(db);
-                let sum = sum_transactions(&filt_transactions);
+                let filt_transactions = self.category_transactions(cat, db);
+                let (sum, converted) = sum_transactions_in(&filt_transactions, db, currency);
                 let cat_name = cat.name().to_string();
 
                 let val = match cat.parent_name(db){
                     Some(parent_name) => {
-                        (parent_name.to_string(), Some(cat_name), filt_transactions.len(), sum)
+                        (parent_name.to_string(), Some(cat_name), filt_transactions.len(), sum, converted)
                     },
                     None => {
-                        (cat_name, None, filt_transactions.len(), sum)
+                        (cat_name, None, filt_transactions.len(), sum, converted)
                     }
                 };
 
@@ -121,13 +296,108 @@ impl Query for QueryReview {
                 .filter_map(|v| if v.2 == 0 {
                     None
                 } else {
-                    Some((v.0.clone(), v.1.clone(), v.3))
+                    Some((v.0.clone(), v.1.clone(), v.3, v.4))
                 })
                 .collect()
         } else {
             vals.iter()
-                .map(|v| (v.0.clone(), v.1.clone(), v.3))
+                .map(|v| (v.0.clone(), v.1.clone(), v.3, v.4))
                 .collect()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accounts_excluded_from_reports() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" flags="0" pos="1" type="1" curr="1" name="Chequing"/>
+<account key="2" flags="64" pos="2" type="1" curr="1" name="Shared"/>
+<cat key="1" flags="0" name="Food"/>
+<ope date="737495" amount="-10" account="1" category="1"/>
+<ope date="737495" amount="-50" account="2" category="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryReview::new(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            false,
+            None,
+            false,
+        );
+        let expected = vec![("Food".to_string(), None, Money::from(-10), false)];
+
+        assert_eq!(expected, query.exec(&db));
+    }
+
+    #[test]
+    fn rollup_subcategories() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" flags="0" pos="1" type="1" curr="1" name="Chequing"/>
+<cat key="1" flags="0" name="Vehicle"/>
+<cat key="2" parent="1" flags="1" name="Gasoline"/>
+<cat key="3" parent="1" flags="1" name="Insurance"/>
+<cat key="4" parent="2" flags="1" name="Premium"/>
+<cat key="5" flags="0" name="Food"/>
+<cat key="6" flags="0" name="Unused"/>
+<ope date="737495" amount="-5" account="1" category="1"/>
+<ope date="737495" amount="-40" account="1" category="2"/>
+<ope date="737495" amount="-60" account="1" category="3"/>
+<ope date="737495" amount="-20" account="1" category="4"/>
+<ope date="737495" amount="-10" account="1" category="5"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryReview::new(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            true,
+            None,
+            true,
+        );
+        let expected = vec![
+            CategoryRollup::new("Food", 0, Money::from(-10), false),
+            CategoryRollup::new("Vehicle", 0, Money::from(-125), false),
+            CategoryRollup::new("Gasoline", 1, Money::from(-60), false),
+            CategoryRollup::new("Premium", 2, Money::from(-20), false),
+            CategoryRollup::new("Insurance", 1, Money::from(-60), false),
+        ];
+
+        assert_eq!(expected, query.exec_rollup(&db));
+    }
+
+    #[test]
+    fn names_are_not_patterns() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" flags="0" pos="1" type="1" curr="1" name="Chequing"/>
+<cat key="1" flags="0" name="Groceries (bulk"/>
+<cat key="2" flags="0" name="Food"/>
+<cat key="3" flags="0" name="Seafood"/>
+<ope date="737495" amount="-30" account="1" category="1"/>
+<ope date="737495" amount="-10" account="1" flags="256" scat="2||3" samt="-4||-6" smem="||"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryReview::new(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            false,
+            None,
+            true,
+        );
+        let expected = vec![
+            CategoryRollup::new("Food", 0, Money::from(-4), false),
+            CategoryRollup::new("Groceries (bulk", 0, Money::from(-30), false),
+            CategoryRollup::new("Seafood", 0, Money::from(-6), false),
+        ];
+
+        assert_eq!(expected, query.exec_rollup(&db));
+    }
+}
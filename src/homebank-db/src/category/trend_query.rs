@@ -0,0 +1,303 @@
+//! Sums across each (sub)category in the [`HomeBankDb`] over time.
+
+use crate::{
+    interval::ReportPeriods, transaction::sum_transactions_in, HomeBankDb, Interval, Money, Query,
+    QueryTransactions, Transaction,
+};
+use chrono::NaiveDate;
+use clap::Parser;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Query the sum of each (sub)category in each period, like HomeBank's "Trend time" report.
+///
+/// Subcategories are rolled up into their parent categories.
+/// Transfers between accounts are left out.
+#[derive(Debug, Parser)]
+pub struct QueryTrend {
+    /// Name of the (sub)categories.
+    #[clap(value_name = "regex")]
+    category: Option<Regex>,
+
+    /// The periods to report the sums for.
+    #[clap(flatten)]
+    periods: ReportPeriods,
+
+    /// Only show (sub)categories down to this depth.
+    /// Use `1` to only show the parent categories.
+    #[clap(long = "depth", value_name = "depth")]
+    depth: Option<usize>,
+}
+
+impl QueryTrend {
+    /// Create a new query for (sub)category sums over time
+    pub fn new(
+        category: Option<Regex>,
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+        interval: Interval,
+        depth: Option<usize>,
+    ) -> Self {
+        Self {
+            category,
+            periods: ReportPeriods::new(date_from, date_to, interval),
+            depth,
+        }
+    }
+
+    /// Retrieve the regular expression for the (sub)category names
+    fn category(&self) -> &Option<Regex> {
+        &self.category
+    }
+
+    /// Retrieve the periods to report the sums for
+    fn periods(&self) -> &ReportPeriods {
+        &self.periods
+    }
+
+    /// Retrieve whether subcategories are shown
+    fn show_subcategories(&self) -> bool {
+        self.depth.unwrap_or(usize::MAX) > 1
+    }
+
+    /// Retrieve the name of each period, in the same order as the sums
+    pub fn labels(&self) -> Vec<String> {
+        let interval = self.periods().interval();
+
+        self.periods()
+            .periods()
+            .iter()
+            .map(|(start, _)| interval.label(start))
+            .collect()
+    }
+}
+
+/// The sums of a (sub)category in each period, in the base currency.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trend {
+    /// Name of the category, or the parent category of a subcategory.
+    category: String,
+
+    /// Name of the subcategory, or `None` for a parent category with its subcategories rolled up.
+    subcategory: Option<String>,
+
+    /// Sum of the (sub)category in each period.
+    sums: Vec<Money>,
+
+    /// Whether any of the sums were converted from another currency.
+    converted: bool,
+}
+
+impl Trend {
+    /// Create a new trend for a (sub)category
+    pub fn new(
+        category: &str,
+        subcategory: Option<&str>,
+        sums: Vec<Money>,
+        converted: bool,
+    ) -> Self {
+        Self {
+            category: category.to_string(),
+            subcategory: subcategory.map(|s| s.to_string()),
+            sums,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the category, or the parent category of a subcategory
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+
+    /// Retrieve the name of the subcategory, if this isn't a parent category
+    pub fn subcategory(&self) -> Option<&str> {
+        self.subcategory.as_deref()
+    }
+
+    /// Retrieve the name of the (sub)category, including the parent category
+    pub fn full_name(&self) -> String {
+        match self.subcategory() {
+            Some(sub) => format!("{}:{}", self.category(), sub),
+            None => self.category().to_string(),
+        }
+    }
+
+    /// Retrieve the sum of the (sub)category in each period
+    pub fn sums(&self) -> &[Money] {
+        &self.sums
+    }
+
+    /// Retrieve the sum of the (sub)category across every period
+    pub fn total(&self) -> Money {
+        self.sums.iter().copied().sum()
+    }
+
+    /// Check if any of the sums were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+impl Query for QueryTrend {
+    type T = Trend;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let base = db.properties().currency();
+        let periods = self.periods().periods();
+
+        // only keep the lines of split transactions that match the category
+        let transaction_query = QueryTransactions::new(
+            &Some(self.periods().date_from()),
+            &Some(self.periods().date_to()),
+            &None,
+            &None,
+            &None,
+            self.category(),
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+        );
+
+        // the lines of each (sub)category in each period
+        let mut rows: BTreeMap<(String, Option<String>), Vec<Vec<Transaction>>> = BTreeMap::new();
+        for tr in transaction_query
+            .exec(db)
+            .iter()
+            .filter(|tr| tr.counts_towards_balance() && !tr.is_transfer())
+            // accounts can be left out of reports, like in HomeBank
+            .filter(|tr| !tr.is_excluded_from_reports(db))
+        {
+            let period_idx = match periods
+                .iter()
+                .position(|(start, end)| tr.date() >= start && tr.date() < end)
+            {
+                Some(idx) => idx,
+                None => continue,
+            };
+
+            for (i, cat_idx) in tr.categories().iter().enumerate() {
+                let cat = match cat_idx.and_then(|idx| db.categories().get(&idx)) {
+                    Some(cat) => cat,
+                    None => continue,
+                };
+                let line = match tr.subset(&[i]) {
+                    Some(line) => line,
+                    None => continue,
+                };
+
+                // subcategories are rolled up into their parent category
+                let mut keys = vec![];
+                match cat.parent_name(db) {
+                    Some(parent_name) => {
+                        keys.push((parent_name.to_string(), None));
+                        if self.show_subcategories() {
+                            keys.push((parent_name.to_string(), Some(cat.name().to_string())));
+                        }
+                    }
+                    None => keys.push((cat.name().to_string(), None)),
+                }
+
+                for key in keys {
+                    rows.entry(key)
+                        .or_insert_with(|| vec![vec![]; periods.len()])[period_idx]
+                        .push(line.clone());
+                }
+            }
+        }
+
+        rows.into_iter()
+            .map(|((category, subcategory), lines)| {
+                let mut converted = false;
+                let sums = lines
+                    .iter()
+                    .map(|period_lines| {
+                        let (sum, period_converted) = sum_transactions_in(period_lines, db, base);
+                        converted |= period_converted;
+                        sum
+                    })
+                    .collect();
+
+                Trend::new(&category, subcategory.as_deref(), sums, converted)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_exec(query: QueryTrend, expected: Vec<(&str, Vec<Money>)>) {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<account key="2" name="Savings"/>
+<cat key="1" name="Food"/>
+<cat key="2" parent="1" name="Groceries"/>
+<cat key="3" parent="1" name="Restaurants"/>
+<cat key="4" name="Fun"/>
+<ope date="737791" amount="-10" account="1" category="1"/>
+<ope date="737792" amount="-30" account="1" flags="256" scat="2||4" samt="-25||-5" smem="||"/>
+<ope date="737793" amount="-500" account="1" dst_account="2" kxfer="1" category="4"/>
+<ope date="737794" amount="-99" account="1" st="4" category="2"/>
+<ope date="737830" amount="-20" account="1" category="3"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let observed: Vec<(String, Vec<Money>)> = query
+            .exec(&db)
+            .iter()
+            .map(|trend| (trend.full_name(), trend.sums().to_vec()))
+            .collect();
+        let expected: Vec<(String, Vec<Money>)> = expected
+            .into_iter()
+            .map(|(name, sums)| (name.to_string(), sums))
+            .collect();
+
+        assert_eq!(expected, observed);
+    }
+
+    fn query(category: Option<&str>, depth: Option<usize>) -> QueryTrend {
+        QueryTrend::new(
+            category.map(|re| Regex::new(re).unwrap()),
+            NaiveDate::from_ymd_opt(2021, 1, 1),
+            NaiveDate::from_ymd_opt(2021, 3, 1),
+            Interval::Month,
+            depth,
+        )
+    }
+
+    #[test]
+    fn rolls_up_subcategories() {
+        let expected = vec![
+            ("Food", vec![Money::from(-35), Money::from(-20)]),
+            ("Food:Groceries", vec![Money::from(-25), Money::ZERO]),
+            ("Food:Restaurants", vec![Money::ZERO, Money::from(-20)]),
+            ("Fun", vec![Money::from(-5), Money::ZERO]),
+        ];
+
+        check_exec(query(None, None), expected);
+    }
+
+    #[test]
+    fn parent_categories_only() {
+        let expected = vec![
+            ("Food", vec![Money::from(-35), Money::from(-20)]),
+            ("Fun", vec![Money::from(-5), Money::ZERO]),
+        ];
+
+        check_exec(query(None, Some(1)), expected);
+    }
+
+    #[test]
+    fn matching_split_lines() {
+        let expected = vec![("Fun", vec![Money::from(-5), Money::ZERO])];
+
+        check_exec(query(Some("^Fun$"), None), expected);
+    }
+}
@@ -3,7 +3,7 @@
 use thiserror::Error;
 
 /// Errors encountered when parsing or formatting [`Currencies`][crate::currency::currency_struct::Currency].
-#[derive(Debug, Error)]
+#[derive(Debug, Error, PartialEq)]
 pub enum CurrencyError {
     /// When the key for a [`Currency`][crate::currency::currency_struct::Currency] is not a number or not found in the database.
     #[error("Invalid currency key.")]
@@ -41,7 +41,7 @@ pub enum CurrencyError {
     #[error("Invalid number of decimals to display.")]
     InvalidDecimalLength,
 
-    /// When the conversion rate for a [`Currency`][crate::currency::currency_struct::Currency] is not a properly parsed `f32`.
+    /// When the conversion rate for a [`Currency`][crate::currency::currency_struct::Currency] is not a properly parsed decimal number.
     #[error("Invalid currency conversion rate.")]
     InvalidConversionRate,
 
@@ -1,9 +1,14 @@
 //! Currencies used within a HomeBank database.
 
 use super::CurrencyError;
-use crate::transaction::julian_date_from_u32;
+use crate::{
+    db::db_writer::xml_attribute,
+    transaction::{julian_date_from_u32, julian_date_to_u32},
+    Money,
+};
 use std::str::FromStr;
 use chrono::NaiveDate;
+use rust_decimal::Decimal;
 use xml::attribute::OwnedAttribute;
 
 /// Currencies used within a HomeBank database.
@@ -21,8 +26,8 @@ pub struct Currency {
     /// The common name for this currency.
     name: String,
 
-    /// The monetary symbol used for this currency, like `$` for the dollar or `€` for the Euro.
-    symbol: char,
+    /// The monetary symbol used for this currency, like `$` for the dollar, `€` for the Euro, or `CHF` for the Swiss franc.
+    symbol: String,
 
     /// Does the currency symbol prefix the amount?
     syprf: bool,
@@ -38,12 +43,13 @@ pub struct Currency {
     /// How many digits should be displayed after the `decimal_separator`?
     decimal_len: usize,
     
-    /// Conversion rate from this currency to the base currency specified in the [`HomeBankDbProperties`][crate::db::db_struct_properties::HomeBankDbProperties].
-    /// `conversion_rate` = `value in base currency` / `value in this currency`.
-    conversion_rate: f32,
+    /// Conversion rate from this currency to the base currency specified in the [`HomeBankDbProperties`][crate::db::db_properties::HomeBankDbProperties].
+    /// `conversion_rate` = `value in this currency` / `value in base currency`, so one unit of the base currency is worth `conversion_rate` units of this currency.
+    /// A rate of `0` means that no rate has been set.
+    conversion_rate: Decimal,
 
-    /// The date when this currency's exchange rates were last updated.
-    mdate: NaiveDate,
+    /// The date when this currency's exchange rates were last updated, if they ever have been.
+    mdate: Option<NaiveDate>,
 }
 
 impl Currency {
@@ -54,24 +60,102 @@ impl Currency {
             flags: 0,
             iso: "".to_string(),
             name: "".to_string(),
-            symbol: '$',
+            symbol: "$".to_string(),
             syprf: false,
             decimal_separator: '.',
             thousands_separator: ' ',
             decimal_len: 2,
-            conversion_rate: 1.0,
-            mdate: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            conversion_rate: Decimal::ONE,
+            mdate: None,
         }
     }
 
     /// Retrieve the `Currency` key
-    pub(crate) fn key(&self) -> usize {
+    pub fn key(&self) -> usize {
         self.key
     }
 
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Retrieve the [ISO Currency Code](https://www.iso.org/iso-4217-currency-codes.html), like `EUR`
+    pub fn iso(&self) -> &str {
+        &self.iso
+    }
+
+    /// Retrieve the monetary symbol, like `$`, `€`, or `CHF`
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Check if the symbol is placed before the amount, like `$1.00`, instead of after it, like `1,00 €`
+    pub fn is_symbol_prefix(&self) -> bool {
+        self.syprf
+    }
+
+    /// Retrieve the character that separates the whole amount from its fractions
+    pub fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// Retrieve the character that groups the thousands digits
+    pub fn thousands_separator(&self) -> char {
+        self.thousands_separator
+    }
+
+    /// Retrieve the conversion rate from the base currency into this `Currency`
+    pub fn conversion_rate(&self) -> Decimal {
+        self.conversion_rate
+    }
+
+    /// Retrieve the number of digits displayed after the decimal separator
+    pub fn decimal_len(&self) -> usize {
+        self.decimal_len
+    }
+
+    /// Round an amount to the number of digits displayed for this `Currency`
+    pub fn round(&self, amount: Money) -> Money {
+        amount.round(self.decimal_len)
+    }
+
+    /// Format an amount the way HomeBank displays it in this `Currency`, like `-$1,234.50`, `CHF 12.00`, or `1 234,50 €`
+    pub fn format(&self, amount: Money) -> String {
+        let decimals = self.decimal_len;
+        let digits = format!("{:.decimals$}", amount.abs());
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        // group the whole digits in threes, starting from the right
+        let mut grouped = String::new();
+        for (i, c) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                grouped.push(self.thousands_separator);
+            }
+            grouped.push(c);
+        }
+        if let Some(fraction) = fraction {
+            grouped.push(self.decimal_separator);
+            grouped.push_str(fraction);
+        }
+
+        // rounding can leave nothing to be negative
+        let sign = if amount.round(decimals).is_negative() {
+            "-"
+        } else {
+            ""
+        };
+        if self.syprf && self.symbol.chars().count() > 1 {
+            // keep a symbol like `CHF` apart from the digits
+            format!("{sign}{} {grouped}", self.symbol)
+        } else if self.syprf {
+            format!("{sign}{}{grouped}", self.symbol)
+        } else {
+            format!("{sign}{grouped} {}", self.symbol)
+        }
+    }
 }
 
 impl Default for Currency {
@@ -119,7 +203,9 @@ impl TryFrom<Vec<OwnedAttribute>> for Currency {
                 }
                 "mdate" => {
                     curr.mdate = match u32::from_str(&i.value) {
-                        Ok(d) => julian_date_from_u32(d),
+                        // HomeBank writes `0` for rates that have never been updated
+                        Ok(0) => None,
+                        Ok(d) => Some(julian_date_from_u32(d)),
                         Err(_) => return Err(CurrencyError::InvalidMDate),
                     };
                 }
@@ -127,10 +213,10 @@ impl TryFrom<Vec<OwnedAttribute>> for Currency {
                     curr.iso = i.value.to_string();
                 }
                 "symb" => {
-                    curr.symbol = match i.value.chars().next() {
-                        Some(c) => c,
-                        None => return Err(CurrencyError::InvalidSymbol),
-                    };
+                    if i.value.is_empty() {
+                        return Err(CurrencyError::InvalidSymbol);
+                    }
+                    curr.symbol = i.value.to_string();
                 }
                 "dchar" => {
                     curr.decimal_separator = match i.value.chars().next() {
@@ -145,8 +231,9 @@ impl TryFrom<Vec<OwnedAttribute>> for Currency {
                     };
                 }
                 "rate" => {
-                    curr.conversion_rate = match f32::from_str(&i.value) {
-                        Ok(f) => f,
+                    // the rate is parsed exactly so that conversions match HomeBank
+                    curr.conversion_rate = match Money::from_str(&i.value) {
+                        Ok(rate) => rate.as_decimal(),
                         Err(_) => return Err(CurrencyError::InvalidConversionRate),
                     }
                 }
@@ -156,3 +243,83 @@ impl TryFrom<Vec<OwnedAttribute>> for Currency {
         Ok(curr)
     }
 }
+
+impl From<&Currency> for Vec<OwnedAttribute> {
+    fn from(curr: &Currency) -> Self {
+        vec![
+            xml_attribute("key", curr.key),
+            xml_attribute("flags", curr.flags),
+            xml_attribute("iso", &curr.iso),
+            xml_attribute("name", &curr.name),
+            xml_attribute("symb", &curr.symbol),
+            xml_attribute("syprf", curr.syprf as usize),
+            xml_attribute("dchar", curr.decimal_separator),
+            xml_attribute("gchar", curr.thousands_separator),
+            xml_attribute("frac", curr.decimal_len),
+            xml_attribute("rate", curr.conversion_rate),
+            xml_attribute("mdate", curr.mdate.as_ref().map_or(0, julian_date_to_u32)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HomeBankDb;
+
+    #[test]
+    fn write_multi_character_symbol() {
+        let input = vec![xml_attribute("key", 1), xml_attribute("symb", "CHF")];
+        let curr = Currency::try_from(input).unwrap();
+        let observed: Vec<OwnedAttribute> = (&curr).into();
+
+        assert_eq!("CHF", curr.symbol());
+        assert!(observed.contains(&xml_attribute("symb", "CHF")));
+    }
+
+    #[track_caller]
+    fn check_format(input: &str, amount: Money, expected: &str) {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+{input}
+</homebank>
+"#
+        );
+        let db = HomeBankDb::from_xml(xml.as_bytes());
+        let observed = db.currencies().get(&1).unwrap().format(amount);
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn format_prefix_symbol() {
+        let input = r#"<cur key="1" iso="USD" name="US Dollar" symb="$" syprf="1" dchar="." gchar="," frac="2" rate="0" mdate="0"/>"#;
+
+        check_format(input, Money::new(-123450, 2), "-$1,234.50");
+        check_format(input, Money::new(99, 2), "$0.99");
+    }
+
+    #[test]
+    fn format_suffix_symbol() {
+        let input = r#"<cur key="1" iso="EUR" name="Euro" symb="€" syprf="0" dchar="," gchar=" " frac="2" rate="0" mdate="0"/>"#;
+
+        check_format(input, Money::new(123456789, 2), "1 234 567,89 €");
+        check_format(input, Money::new(-1, 3), "0,00 €");
+    }
+
+    #[test]
+    fn format_without_decimals() {
+        let input = r#"<cur key="1" iso="JPY" name="Yen" symb="¥" syprf="1" dchar="." gchar="," frac="0" rate="0" mdate="0"/>"#;
+
+        check_format(input, Money::from(-1000), "-¥1,000");
+    }
+
+    #[test]
+    fn format_multi_character_symbol() {
+        let input = r#"<cur key="1" iso="CHF" name="Swiss Franc" symb="CHF" syprf="1" dchar="." gchar="'" frac="2" rate="0" mdate="0"/>"#;
+
+        check_format(input, Money::from(12), "CHF 12.00");
+        check_format(input, Money::new(-123450, 2), "-CHF 1'234.50");
+    }
+}
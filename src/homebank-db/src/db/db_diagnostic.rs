@@ -0,0 +1,147 @@
+//! Diagnostics for elements in the HomeBank XML file that could not be parsed.
+
+use super::{db_properties::HomeBankDbPropertiesError, HomeBankDbError};
+use crate::{
+    group::GroupError, AccountError, AssignmentError, CategoryError, CurrencyError, PayeeError,
+    TagError, TemplateError, TransactionError,
+};
+use std::fmt;
+use thiserror::Error;
+use xml::{attribute::OwnedAttribute, common::TextPosition};
+
+/// The reason an element in the HomeBank XML file could not be parsed.
+#[derive(Debug, Error, PartialEq)]
+pub enum ElementError {
+    /// The `<homebank>` element holding the database version.
+    #[error(transparent)]
+    Schema(#[from] HomeBankDbError),
+
+    /// The `<properties>` element.
+    #[error(transparent)]
+    Properties(#[from] HomeBankDbPropertiesError),
+
+    /// A `<cur>` element.
+    #[error(transparent)]
+    Currency(#[from] CurrencyError),
+
+    /// A `<grp>` element.
+    #[error(transparent)]
+    Group(#[from] GroupError),
+
+    /// An `<account>` element.
+    #[error(transparent)]
+    Account(#[from] AccountError),
+
+    /// A `<pay>` element.
+    #[error(transparent)]
+    Payee(#[from] PayeeError),
+
+    /// A `<cat>` element.
+    #[error(transparent)]
+    Category(#[from] CategoryError),
+
+    /// A `<tag>` element.
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    /// An `<asg>` element.
+    #[error(transparent)]
+    Assignment(#[from] AssignmentError),
+
+    /// A `<fav>` element.
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+
+    /// An `<ope>` element.
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+
+    /// The file itself is not well-formed XML.
+    /// Nothing after this point in the file can be read.
+    #[error("Malformed XML: {0}")]
+    Xml(String),
+}
+
+/// An element in the HomeBank XML file that could not be parsed, and where it can be found.
+#[derive(Debug, Error, PartialEq)]
+pub struct ParseDiagnostic {
+    /// Name of the XML element, such as `ope` or `cat`.
+    element: String,
+
+    /// The `key` attribute of the element, if it has one.
+    key: Option<String>,
+
+    /// Position of the element in the file.
+    position: TextPosition,
+
+    /// Why the element could not be parsed.
+    #[source]
+    error: ElementError,
+}
+
+impl ParseDiagnostic {
+    /// Create a new diagnostic for an element.
+    pub fn new(
+        element: &str,
+        key: Option<String>,
+        position: TextPosition,
+        error: ElementError,
+    ) -> Self {
+        Self {
+            element: element.to_string(),
+            key,
+            position,
+            error,
+        }
+    }
+
+    /// Create a new diagnostic from the attributes of an element, using its `key` attribute if it has one.
+    pub(crate) fn from_attributes<E: Into<ElementError>>(
+        element: &str,
+        attributes: &[OwnedAttribute],
+        position: TextPosition,
+        error: E,
+    ) -> Self {
+        let key = attributes
+            .iter()
+            .find(|att| att.name.local_name == "key")
+            .map(|att| att.value.to_string());
+
+        Self::new(element, key, position, error.into())
+    }
+
+    /// Retrieve the name of the XML element.
+    pub fn element(&self) -> &str {
+        &self.element
+    }
+
+    /// Retrieve the `key` attribute of the XML element, if it has one.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Retrieve the line number where the element starts, counting from 1.
+    pub fn line(&self) -> u64 {
+        self.position.row + 1
+    }
+
+    /// Retrieve the column number where the element starts, counting from 1.
+    pub fn column(&self) -> u64 {
+        self.position.column + 1
+    }
+
+    /// Retrieve the reason the element could not be parsed.
+    pub fn error(&self) -> &ElementError {
+        &self.error
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: <{}>", self.line(), self.column(), self.element())?;
+        if let Some(key) = self.key() {
+            write!(f, " (key {key})")?;
+        }
+        write!(f, ": {}", self.error())
+    }
+}
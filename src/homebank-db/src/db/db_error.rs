@@ -1,5 +1,6 @@
 //! Errors when parsing or loading a HomeBank database XML file.
 
+use super::ParseDiagnostic;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -22,6 +23,36 @@ pub enum HomeBankDbError {
     #[error("Error parsing XHB file `{0}`.")]
     CouldNotParse(PathBuf),
 
+    /// The database file was read, but some of its elements could not be parsed.
+    /// This is only raised when loading the database strictly.
+    #[error("XHB file `{0}` contains {} element(s) that could not be parsed.", .1.len())]
+    InvalidElements(PathBuf, Vec<ParseDiagnostic>),
+
+    /// The database file is not well-formed XML, so nothing after the given position could be read.
+    /// This is raised even when loading the database leniently.
+    #[error("XHB file `{0}` is not well-formed XML, at {1}")]
+    MalformedXml(PathBuf, Box<ParseDiagnostic>),
+
+    /// No [`Currency`][crate::currency::currency_struct::Currency] in the database has the given ISO code.
+    #[error("No currency with the ISO code `{0}` in the HomeBank file.")]
+    UnknownCurrency(String),
+
+    /// Amounts in the first [`Currency`][crate::currency::currency_struct::Currency] cannot be converted into the second, since one of them has no conversion rate.
+    #[error("Amounts in `{0}` cannot be converted into `{1}`, since there is no conversion rate between them.")]
+    CannotConvert(String, String),
+
+    /// Writing the database back out would lose or change something in the file it was loaded from.
+    #[error("Writing XHB file `{0}` would not keep it as it is, at {1}.")]
+    WouldNotRoundTrip(PathBuf, String),
+
+    /// The database could not be written out to the file.
+    #[error("Error writing XHB file `{0}`.")]
+    CouldNotWrite(PathBuf),
+
+    /// The database could not be serialized into HomeBank XML.
+    #[error("Error serializing database to XML.")]
+    CouldNotSerialize,
+
     /// The last saved date of the database cannot be converted to a `NaiveDate` type.
     #[error("Invalid database date.")]
     InvalidDate,
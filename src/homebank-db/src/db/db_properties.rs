@@ -1,11 +1,13 @@
 //! Properties for the entire HomeBank database.
 
+use super::db_writer::xml_attribute;
+use chrono::{Datelike, Duration, Months, NaiveDate};
 use std::str::FromStr;
 use thiserror::Error;
 use xml::attribute::OwnedAttribute;
 
 /// Properties for the entire HomeBank database.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct HomeBankDbProperties {
     /// Title for the database.
     title: String,
@@ -18,6 +20,12 @@ pub struct HomeBankDbProperties {
 
     /// Mode for how automatically scheduled transactions should be added.
     sched_mode: ScheduleMode,
+
+    /// `auto_weekday` as it was found in the file, kept when the `AddUntil` mode isn't in use.
+    auto_weekday: Option<u8>,
+
+    /// `auto_nbdays` as it was found in the file, kept when the `Add` mode isn't in use.
+    auto_nbdays: Option<u8>,
 }
 
 impl HomeBankDbProperties {
@@ -28,6 +36,8 @@ impl HomeBankDbProperties {
             currency_key: 1,
             car_category_key: 1,
             sched_mode: ScheduleMode::NotCurrentlySet(None, None),
+            auto_weekday: None,
+            auto_nbdays: None,
         }
     }
 
@@ -43,8 +53,35 @@ impl HomeBankDbProperties {
             currency_key: currency,
             car_category_key: car_category,
             sched_mode,
+            auto_weekday: None,
+            auto_nbdays: None,
         }
     }
+
+    /// Retrieve the title of the database
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Retrieve the key of the base [`Currency`][crate::currency::currency_struct::Currency]
+    pub fn currency(&self) -> usize {
+        self.currency_key
+    }
+
+    /// Retrieve the default mode for adding scheduled transactions
+    pub fn sched_mode(&self) -> &ScheduleMode {
+        &self.sched_mode
+    }
+}
+
+impl PartialEq for HomeBankDbProperties {
+    fn eq(&self, other: &Self) -> bool {
+        // the value for the scheduling mode that isn't in use doesn't change the properties
+        self.title == other.title
+            && self.currency_key == other.currency_key
+            && self.car_category_key == other.car_category_key
+            && self.sched_mode == other.sched_mode
+    }
 }
 
 impl Default for HomeBankDbProperties {
@@ -127,6 +164,7 @@ impl TryFrom<Vec<OwnedAttribute>> for HomeBankDbProperties {
                     }
                 }
                 "auto_weekday" => {
+                    props.auto_weekday = u8::from_str(&i.value).ok();
                     match (u8::from_str(&i.value), props.sched_mode) {
                         (Ok(idx), ScheduleMode::NotCurrentlySet(_, add_val)) => {
                             // store the value temporarily until you potentially run across the `auto_smode`
@@ -144,6 +182,7 @@ impl TryFrom<Vec<OwnedAttribute>> for HomeBankDbProperties {
                     }
                 }
                 "auto_nbdays" => {
+                    props.auto_nbdays = u8::from_str(&i.value).ok();
                     match (u8::from_str(&i.value), props.sched_mode) {
                         (Ok(idx), ScheduleMode::NotCurrentlySet(adduntil_val, _)) => {
                             // store the value temporarily until you potentially run across the `auto_smode`
@@ -167,8 +206,43 @@ impl TryFrom<Vec<OwnedAttribute>> for HomeBankDbProperties {
     }
 }
 
+impl From<&HomeBankDbProperties> for Vec<OwnedAttribute> {
+    fn from(props: &HomeBankDbProperties) -> Self {
+        let mut v = vec![
+            xml_attribute("title", &props.title),
+            xml_attribute("curr", props.currency_key),
+            xml_attribute("car_category", props.car_category_key),
+        ];
+
+        // the value for the mode that isn't in use is written back out as it was found
+        let (smode, weekday, nb_days) = match props.sched_mode {
+            // HomeBank leaves out a number of days of 0
+            ScheduleMode::Add(nb_days) => (
+                Some(1),
+                props.auto_weekday,
+                Some(nb_days).filter(|&n| n != 0),
+            ),
+            ScheduleMode::AddUntil(weekday) => (Some(0), Some(weekday), props.auto_nbdays),
+            // only write out the values that were found, without committing to a mode
+            ScheduleMode::NotCurrentlySet(weekday, nb_days) => (None, weekday, nb_days),
+        };
+
+        if let Some(smode) = smode {
+            v.push(xml_attribute("auto_smode", smode));
+        }
+        if let Some(weekday) = weekday {
+            v.push(xml_attribute("auto_weekday", weekday));
+        }
+        if let Some(nb_days) = nb_days {
+            v.push(xml_attribute("auto_nbdays", nb_days));
+        }
+
+        v
+    }
+}
+
 /// Default setting for how scheduled [`Transaction`][crate::transaction::transaction_struct::Transaction] dates should be calculated.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ScheduleMode {
     /// Not currently set.
     /// This is used when creating a new HomeBank database, or when parsing the XML file for the first time.
@@ -183,6 +257,42 @@ pub enum ScheduleMode {
     AddUntil(u8)
 }
 
+impl ScheduleMode {
+    /// Find the last date, included, that scheduled transactions are added up to on a given day.
+    ///
+    /// When the mode is not set, no days are added in advance.
+    pub fn post_limit(&self, today: &NaiveDate) -> NaiveDate {
+        match self {
+            ScheduleMode::Add(nb_days) => *today + Duration::days((*nb_days).into()),
+            ScheduleMode::AddUntil(weekday) => {
+                // like HomeBank, stop before this day of the current month, or of the next month if it has already passed
+                let day = u32::from(*weekday);
+                let mut until = day_of_month(today, day);
+                if today.day() > until.day() {
+                    let next_month = until.checked_add_months(Months::new(1)).unwrap_or(until);
+                    until = day_of_month(&next_month, day);
+                }
+
+                until - Duration::days(1)
+            }
+            ScheduleMode::NotCurrentlySet(_, nb_days) => {
+                *today + Duration::days(nb_days.unwrap_or(0).into())
+            }
+        }
+    }
+}
+
+/// Find a day in the same month as `date`, using the last day of the month when it's too short for `day`.
+fn day_of_month(date: &NaiveDate, day: u32) -> NaiveDate {
+    let first = date.with_day(1).unwrap_or(*date);
+    let last = first
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(first);
+
+    first.with_day(day.clamp(1, last.day())).unwrap_or(last)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,4 +417,44 @@ mod tests {
             check_try_from_single_str(input, &expected);
         }
     }
+
+    #[track_caller]
+    fn check_post_limit(mode: ScheduleMode, today: (i32, u32, u32), expected: (i32, u32, u32)) {
+        let today = NaiveDate::from_ymd_opt(today.0, today.1, today.2).unwrap();
+        let expected = NaiveDate::from_ymd_opt(expected.0, expected.1, expected.2).unwrap();
+
+        assert_eq!(expected, mode.post_limit(&today));
+    }
+
+    #[test]
+    fn post_limit_add() {
+        check_post_limit(ScheduleMode::Add(0), (2023, 3, 10), (2023, 3, 10));
+        check_post_limit(ScheduleMode::Add(5), (2023, 3, 30), (2023, 4, 4));
+    }
+
+    #[test]
+    fn post_limit_add_until_this_month() {
+        check_post_limit(ScheduleMode::AddUntil(15), (2023, 3, 10), (2023, 3, 14));
+    }
+
+    #[test]
+    fn post_limit_add_until_next_month() {
+        check_post_limit(ScheduleMode::AddUntil(1), (2023, 3, 10), (2023, 3, 31));
+        check_post_limit(ScheduleMode::AddUntil(5), (2023, 12, 20), (2024, 1, 4));
+    }
+
+    #[test]
+    fn post_limit_add_until_short_month() {
+        // February doesn't have a 31st, so stop before its last day
+        check_post_limit(ScheduleMode::AddUntil(31), (2023, 2, 10), (2023, 2, 27));
+        check_post_limit(ScheduleMode::AddUntil(30), (2024, 2, 10), (2024, 2, 28));
+        check_post_limit(ScheduleMode::AddUntil(31), (2023, 4, 30), (2023, 4, 29));
+        // once the 30th has passed, the next month is February, so stop before its last day
+        check_post_limit(ScheduleMode::AddUntil(30), (2023, 1, 31), (2023, 2, 27));
+    }
+
+    #[test]
+    fn post_limit_not_set() {
+        check_post_limit(ScheduleMode::NotCurrentlySet(Some(1), None), (2023, 3, 10), (2023, 3, 10));
+    }
 }
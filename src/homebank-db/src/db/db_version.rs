@@ -1,7 +1,9 @@
 //! Version information for the HomeBank database.
 
-use super::HomeBankDbError;
-use crate::transaction::{julian_date_from_u32, transaction_date::unclamped_julian_date_from_u32};
+use super::{db_writer::xml_attribute, HomeBankDbError};
+use crate::transaction::{
+    julian_date_from_u32, julian_date_to_u32, transaction_date::unclamped_julian_date_from_u32,
+};
 use chrono::NaiveDate;
 use semver::Version;
 use std::str::FromStr;
@@ -12,6 +14,9 @@ use xml::attribute::OwnedAttribute;
 pub struct HomeBankDbSchema {
     version: Version,
     date: NaiveDate,
+
+    /// The version exactly as it was written in the file, so that it can be written back out unchanged.
+    version_text: Option<String>,
 }
 
 impl HomeBankDbSchema {
@@ -20,6 +25,7 @@ impl HomeBankDbSchema {
         Self {
             version: Version::new(0, 0, 1),
             date: julian_date_from_u32(50504),
+            version_text: None,
         }
     }
 }
@@ -42,10 +48,8 @@ impl TryFrom<Vec<OwnedAttribute>> for HomeBankDbSchema {
                     // The version is stored internally as a f32 type, but when
                     // it's written out in text, it carries all the floating points.
                     // This leads to a not nicely-formatted value that needs to be parsed manually.
-                    db_ver.version = match parse_version_string(&i.value) {
-                        Ok(ver) => ver,
-                        Err(e) => return Err(e),
-                    }
+                    db_ver.version = parse_version_string(&i.value)?;
+                    db_ver.version_text = Some(i.value.to_string());
                 }
                 "d" => match u32::from_str(&i.value) {
                     Ok(d) => db_ver.date = unclamped_julian_date_from_u32(d),
@@ -59,6 +63,23 @@ impl TryFrom<Vec<OwnedAttribute>> for HomeBankDbSchema {
     }
 }
 
+impl From<&HomeBankDbSchema> for Vec<OwnedAttribute> {
+    fn from(db_ver: &HomeBankDbSchema) -> Self {
+        vec![
+            match &db_ver.version_text {
+                Some(text) => xml_attribute("v", text),
+                None => xml_attribute("v", version_string(&db_ver.version)),
+            },
+            xml_attribute("d", format!("{:06}", julian_date_to_u32(&db_ver.date))),
+        ]
+    }
+}
+
+/// Write the version the way HomeBank stores it, as a `major.minor` floating point number.
+fn version_string(v: &Version) -> String {
+    format!("{}.{}", v.major, v.minor)
+}
+
 fn parse_version_string(s: &str) -> Result<Version, HomeBankDbError> {
     match f32::from_str(s) {
         Ok(f) => {
@@ -110,6 +131,7 @@ mod tests {
         let input = r#"<homebank v="1.3999999999999999">"#;
         let expected = Ok(HomeBankDbSchema {
             version: Version::new(1, 4, 0),
+            version_text: Some(String::from("1.3999999999999999")),
             ..Default::default()
         });
 
@@ -0,0 +1,324 @@
+//! Write a [`HomeBankDb`] back out to a HomeBank XML file.
+
+use super::{HomeBankDb, HomeBankDbError};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+use xml::{
+    attribute::OwnedAttribute,
+    common::Position,
+    name::{Name, OwnedName},
+    namespace::Namespace,
+    reader::{self, EventReader},
+    writer::{EmitterConfig, EventWriter, XmlEvent},
+};
+
+/// Create a single attribute for an element in the HomeBank XML file.
+pub(crate) fn xml_attribute<T: ToString>(name: &str, value: T) -> OwnedAttribute {
+    OwnedAttribute::new(OwnedName::local(name), value.to_string())
+}
+
+impl HomeBankDb {
+    /// Write the database to an XHB file, replacing it if it already exists.
+    ///
+    /// The database is written to a temporary file next to it first, which then replaces the original file.
+    /// If anything goes wrong, the original file is left as it was.
+    ///
+    /// Anything in the original file that isn't part of the database is lost, so use [`check_round_trip`][HomeBankDb::check_round_trip] before changing the database.
+    pub fn write_to_path(&self, path: &Path) -> Result<(), HomeBankDbError> {
+        let tmp_path = temporary_path(path);
+
+        let written = self
+            .write_new_file(&tmp_path)
+            .and_then(|_| match fs::metadata(path) {
+                // keep the permissions of the file being replaced
+                Ok(meta) => fs::set_permissions(&tmp_path, meta.permissions()),
+                Err(_) => Ok(()),
+            })
+            .and_then(|_| fs::rename(&tmp_path, path));
+
+        match written {
+            Ok(_) => Ok(()),
+            Err(_) => {
+                // the temporary file may not exist, depending on what failed
+                let _ = fs::remove_file(&tmp_path);
+                Err(HomeBankDbError::CouldNotWrite(path.to_path_buf()))
+            }
+        }
+    }
+
+    /// Write the database to a new file, making sure it is on disk before returning.
+    fn write_new_file(&self, path: &Path) -> std::io::Result<()> {
+        let xhb_file = File::create(path)?;
+        let mut writer = BufWriter::new(xhb_file);
+
+        self.write_xml(&mut writer)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        writer.flush()?;
+        writer.get_ref().sync_all()
+    }
+
+    /// Check that writing the database back out would keep every element and attribute in the XHB file it was loaded from.
+    ///
+    /// Use this before changing the database and writing it to the same file with [`write_to_path`][HomeBankDb::write_to_path].
+    /// The error describes the first element that would be lost, added, or changed.
+    pub fn check_round_trip(&self, path: &Path) -> Result<(), HomeBankDbError> {
+        let xhb_file = match File::open(path) {
+            Ok(f) => f,
+            Err(_) => return Err(HomeBankDbError::CouldNotOpen(path.to_path_buf())),
+        };
+
+        match self.round_trip_difference(BufReader::new(xhb_file)) {
+            Ok(None) => Ok(()),
+            Ok(Some(difference)) => Err(HomeBankDbError::WouldNotRoundTrip(
+                path.to_path_buf(),
+                difference,
+            )),
+            Err(_) => Err(HomeBankDbError::CouldNotRead(path.to_path_buf())),
+        }
+    }
+
+    /// Describe the first difference between the elements in some HomeBank XML and the database written back out, if there is any.
+    pub(crate) fn round_trip_difference<R: Read>(
+        &self,
+        source: R,
+    ) -> Result<Option<String>, HomeBankDbError> {
+        let original = read_elements(source).map_err(|_| HomeBankDbError::CouldNotSerialize)?;
+
+        let mut buf = Vec::new();
+        self.write_xml(&mut buf)?;
+        let written =
+            read_elements(buf.as_slice()).map_err(|_| HomeBankDbError::CouldNotSerialize)?;
+
+        Ok(first_difference(&original, &written))
+    }
+
+    /// Write the database as HomeBank XML into any destination.
+    ///
+    /// Every element is written on its own line, in the same order that HomeBank itself saves them.
+    /// Entities stored by their key are written in ascending key order so that the output is stable.
+    pub fn write_xml<W: Write>(&self, sink: W) -> Result<(), HomeBankDbError> {
+        let mut writer = EmitterConfig::new()
+            .perform_indent(true)
+            .indent_string("")
+            .pad_self_closing(false)
+            .create_writer(sink);
+
+        match self.write_events(&mut writer) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(HomeBankDbError::CouldNotSerialize),
+        }
+    }
+
+    /// Emit every XML event for the database.
+    fn write_events<W: Write>(&self, w: &mut EventWriter<W>) -> xml::writer::Result<()> {
+        w.write(XmlEvent::StartDocument {
+            version: xml::common::XmlVersion::Version10,
+            encoding: Some("UTF-8"),
+            standalone: None,
+        })?;
+
+        write_start_element(w, "homebank", self.version().into())?;
+
+        write_empty_element(w, "properties", self.properties().into())?;
+
+        for (_, curr) in sorted_by_key(self.currencies()) {
+            write_empty_element(w, "cur", curr.into())?;
+        }
+        for (_, grp) in sorted_by_key(self.groups()) {
+            write_empty_element(w, "grp", grp.into())?;
+        }
+        for (_, acct) in sorted_by_key(self.accounts()) {
+            write_empty_element(w, "account", acct.into())?;
+        }
+        for (_, payee) in sorted_by_key(self.payees()) {
+            write_empty_element(w, "pay", payee.into())?;
+        }
+        for (_, cat) in sorted_by_key(self.categories()) {
+            write_empty_element(w, "cat", cat.into())?;
+        }
+        for (_, tag) in sorted_by_key(self.tags()) {
+            write_empty_element(w, "tag", tag.into())?;
+        }
+        for (_, tmpl) in sorted_by_key(self.templates()) {
+            write_empty_element(w, "fav", tmpl.into())?;
+        }
+        for (_, asg) in sorted_by_key(self.assignments()) {
+            write_empty_element(w, "asg", asg.into())?;
+        }
+        for tr in self.transactions() {
+            write_empty_element(w, "ope", tr.into())?;
+        }
+
+        w.write(XmlEvent::end_element())?;
+        // HomeBank ends the file with a trailing newline
+        w.inner_mut().write_all(b"\n")?;
+
+        Ok(())
+    }
+}
+
+/// An element read from HomeBank XML, with its attributes by name.
+#[derive(Debug, PartialEq)]
+struct XmlElement {
+    /// Name of the element, like `account` or `ope`.
+    name: String,
+
+    /// Line in the file that the element starts on, counting from 1.
+    line: u64,
+
+    /// Value of each attribute, by name.
+    attributes: BTreeMap<String, String>,
+}
+
+impl XmlElement {
+    /// Describe the element the way it starts in the file, with its key if it has one (e.g. `<account key="2">`).
+    fn describe(&self) -> String {
+        match self.attributes.get("key") {
+            Some(key) => format!("<{} key=\"{}\">", self.name, key),
+            None => format!("<{}>", self.name),
+        }
+    }
+}
+
+/// Read every element in some HomeBank XML, in the order they appear.
+fn read_elements<R: Read>(source: R) -> Result<Vec<XmlElement>, reader::Error> {
+    let mut parser = EventReader::new(source);
+    let mut elements = vec![];
+
+    loop {
+        match parser.next()? {
+            reader::XmlEvent::StartElement {
+                name, attributes, ..
+            } => elements.push(XmlElement {
+                name: name.local_name,
+                line: parser.position().row + 1,
+                attributes: attributes
+                    .into_iter()
+                    .map(|att| (att.name.local_name, att.value))
+                    .collect(),
+            }),
+            reader::XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+
+    Ok(elements)
+}
+
+/// Describe the first element from the original XML that would be lost or changed, or that would be added.
+///
+/// Elements are matched up by their name and key, or by their order when they don't have a key.
+fn first_difference(original: &[XmlElement], written: &[XmlElement]) -> Option<String> {
+    // group the written elements by name and key, keeping their order
+    let mut unmatched: BTreeMap<(&str, Option<&str>), Vec<&XmlElement>> = BTreeMap::new();
+    for elem in written.iter().rev() {
+        unmatched
+            .entry((&elem.name, elem.attributes.get("key").map(|k| k.as_str())))
+            .or_default()
+            .push(elem);
+    }
+
+    for elem in original {
+        let id = (
+            elem.name.as_str(),
+            elem.attributes.get("key").map(|k| k.as_str()),
+        );
+        let other = match unmatched.get_mut(&id).and_then(|v| v.pop()) {
+            Some(other) => other,
+            None => {
+                return Some(format!(
+                    "line {}, {}: the element would be dropped",
+                    elem.line,
+                    elem.describe()
+                ))
+            }
+        };
+
+        for (name, value) in &elem.attributes {
+            match other.attributes.get(name) {
+                None => {
+                    return Some(format!(
+                        "line {}, {}: attribute `{}` would be dropped",
+                        elem.line,
+                        elem.describe(),
+                        name
+                    ))
+                }
+                Some(other_value) if other_value != value => {
+                    return Some(format!(
+                        "line {}, {}: attribute `{}` would change from `{}` to `{}`",
+                        elem.line,
+                        elem.describe(),
+                        name,
+                        value,
+                        other_value
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(name) = other
+            .attributes
+            .keys()
+            .find(|name| !elem.attributes.contains_key(*name))
+        {
+            return Some(format!(
+                "line {}, {}: attribute `{}` would be added",
+                elem.line,
+                elem.describe(),
+                name
+            ));
+        }
+    }
+
+    unmatched
+        .values()
+        .flatten()
+        .next()
+        .map(|elem| format!("{}: the element would be added", elem.describe()))
+}
+
+/// Path of the temporary file to write before replacing the file at `path`, in the same directory so it can be renamed over it.
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(".tmp");
+
+    path.with_file_name(name)
+}
+
+/// Sort the values of a keyed map by their key.
+fn sorted_by_key<T>(map: &HashMap<usize, T>) -> Vec<(&usize, &T)> {
+    let mut v: Vec<(&usize, &T)> = map.iter().collect();
+    v.sort_by_key(|(&k, _)| k);
+
+    v
+}
+
+/// Write the opening tag of an element.
+fn write_start_element<W: Write>(
+    w: &mut EventWriter<W>,
+    name: &str,
+    attributes: Vec<OwnedAttribute>,
+) -> xml::writer::Result<()> {
+    w.write(XmlEvent::StartElement {
+        name: Name::local(name),
+        attributes: Cow::Owned(attributes.iter().map(|a| a.borrow()).collect()),
+        namespace: Cow::Owned(Namespace::empty()),
+    })
+}
+
+/// Write an element that only contains attributes (i.e. `<name attr="value"/>`).
+fn write_empty_element<W: Write>(
+    w: &mut EventWriter<W>,
+    name: &str,
+    attributes: Vec<OwnedAttribute>,
+) -> xml::writer::Result<()> {
+    write_start_element(w, name, attributes)?;
+    w.write(XmlEvent::end_element())
+}
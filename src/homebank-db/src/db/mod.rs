@@ -1,11 +1,14 @@
 //! Data structure for the HomeBank database.
 
+pub mod db_diagnostic;
 pub mod db_error;
 pub mod db_properties;
 pub mod db_struct;
 pub mod db_version;
+pub mod db_writer;
 
 pub use db_struct::HomeBankDb;
+pub use db_diagnostic::{ElementError, ParseDiagnostic};
 pub use db_error::HomeBankDbError;
-pub use db_properties::HomeBankDbProperties;
+pub use db_properties::{HomeBankDbProperties, ScheduleMode};
 pub use db_version::HomeBankDbSchema;
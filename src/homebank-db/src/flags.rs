@@ -0,0 +1,77 @@
+//! Sets of flags, stored as bits in the HomeBank XML file.
+
+/// Define a set of flags that is stored as bits in the HomeBank XML file, with a constant for each known flag.
+///
+/// The sets are plain wrappers around the raw bits, instead of enums of the known flags,
+/// so that any bits HomeBank adds in the future survive reading and writing the file.
+macro_rules! flag_set {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$flag_meta:meta])*
+                const $flag:ident = $bits:expr;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        ///
+        /// Bits that aren't listed here are kept as they are, so that writing the database back out doesn't lose them.
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name(usize);
+
+        impl $name {
+            $(
+                $(#[$flag_meta])*
+                pub const $flag: Self = Self($bits);
+            )*
+
+            /// Create a set with no flags.
+            pub fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Retrieve the raw bits, as stored in the database.
+            pub fn bits(&self) -> usize {
+                self.0
+            }
+
+            /// Check if every flag in `other` is set.
+            pub fn contains(&self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Set every flag in `other`.
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            /// Clear every flag in `other`.
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+        }
+
+        impl From<usize> for $name {
+            fn from(bits: usize) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+pub(crate) use flag_set;
@@ -3,7 +3,7 @@
 use thiserror::Error;
 
 /// Errors when parsing [`Group`s][crate::group::group_struct::Group] from the HomeBank XML file.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, PartialEq)]
 pub enum GroupError {
     /// When the key for the group is an invalid number.
     #[error("Invalid group key.")]
@@ -1,6 +1,7 @@
 //! User-provided groups that an [`Account`][crate::account::account_struct::Account] belongs to.
 
 use super::GroupError;
+use crate::db::db_writer::xml_attribute;
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
 
@@ -68,3 +69,12 @@ impl TryFrom<Vec<OwnedAttribute>> for Group {
         Ok(grp)
     }
 }
+
+impl From<&Group> for Vec<OwnedAttribute> {
+    fn from(grp: &Group) -> Self {
+        vec![
+            xml_attribute("key", grp.key),
+            xml_attribute("name", &grp.name),
+        ]
+    }
+}
@@ -0,0 +1,258 @@
+//! Dates given as calendar dates, relative to today, or as whole periods.
+
+use super::{Interval, IntervalError};
+use crate::category::TODAY;
+use chrono::{Duration, Months, NaiveDate};
+use std::str::FromStr;
+
+/// A day or a span of days, given as an expression.
+///
+/// Expressions can be:
+///
+/// - a date, like `2026-03-14`, `today`, `yesterday`, or `tomorrow`
+/// - a number of days, weeks, months, or years before today, like `-30d`, `-2w`, `-3m`, or `-1y`
+/// - a calendar period, like `2026`, `2026-Q2`, or `2026-03`
+/// - a period relative to today, like `this-month`, `last-quarter`, or `next-week`
+/// - the year, quarter, month, or week to date, with `ytd`, `qtd`, `mtd`, or `wtd`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DateExpr {
+    /// First day covered by the expression.
+    start: NaiveDate,
+
+    /// Day after the last day covered by the expression.
+    end: NaiveDate,
+
+    /// Whether the expression names a whole period, instead of a single day.
+    period: bool,
+}
+
+impl DateExpr {
+    /// Create a new expression for a single day
+    pub fn day(date: NaiveDate) -> Self {
+        Self {
+            start: date,
+            end: date.succ_opt().unwrap_or(NaiveDate::MAX),
+            period: false,
+        }
+    }
+
+    /// Create a new expression for a whole period, given by its first day and the day after its last day
+    pub fn period(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start,
+            end,
+            period: true,
+        }
+    }
+
+    /// Parse an expression, with relative dates counted from `today`.
+    pub fn parse(s: &str, today: &NaiveDate) -> Result<Self, IntervalError> {
+        let invalid = || IntervalError::InvalidDate(s.to_string());
+        let tomorrow = today.succ_opt().unwrap_or(NaiveDate::MAX);
+        let whole = |interval: Interval, date: &NaiveDate| {
+            Self::period(interval.start_of(date), interval.next_start(date))
+        };
+        let to_date = |interval: Interval| Self::period(interval.start_of(today), tomorrow);
+        let unit = |name: &str| Interval::from_str(name).map_err(|_| invalid());
+
+        match s {
+            "today" => return Ok(Self::day(*today)),
+            "yesterday" => return today.pred_opt().map(Self::day).ok_or_else(invalid),
+            "tomorrow" => return Ok(Self::day(tomorrow)),
+            "ytd" => return Ok(to_date(Interval::Year)),
+            "qtd" => return Ok(to_date(Interval::Quarter)),
+            "mtd" => return Ok(to_date(Interval::Month)),
+            "wtd" => return Ok(to_date(Interval::Week)),
+            _ => {}
+        }
+
+        if let Some(name) = s.strip_prefix("this-") {
+            return Ok(whole(unit(name)?, today));
+        }
+        if let Some(name) = s.strip_prefix("last-") {
+            let interval = unit(name)?;
+            let previous = interval.start_of(today).pred_opt().ok_or_else(invalid)?;
+            return Ok(whole(interval, &previous));
+        }
+        if let Some(name) = s.strip_prefix("next-") {
+            let interval = unit(name)?;
+            return Ok(whole(interval, &interval.next_start(today)));
+        }
+
+        // a number of days, weeks, months, or years ago, up to and including today
+        if let Some(ago) = s.strip_prefix('-') {
+            let (n, unit) = ago
+                .char_indices()
+                .last()
+                .map(|(i, _)| ago.split_at(i))
+                .ok_or_else(invalid)?;
+            let n: u32 = n.parse().map_err(|_| invalid())?;
+            let start = match unit {
+                "d" => today.checked_sub_signed(Duration::days(n.into())),
+                "w" => today.checked_sub_signed(Duration::weeks(n.into())),
+                "m" => today.checked_sub_months(Months::new(n)),
+                "y" => n
+                    .checked_mul(12)
+                    .and_then(|months| today.checked_sub_months(Months::new(months))),
+                _ => None,
+            }
+            .ok_or_else(invalid)?;
+
+            return Ok(Self {
+                start,
+                end: tomorrow,
+                period: false,
+            });
+        }
+
+        if let Ok(date) = NaiveDate::from_str(s) {
+            return Ok(Self::day(date));
+        }
+
+        // calendar periods, like `2026`, `2026-Q2`, or `2026-03`
+        let mut parts = s.splitn(2, '-');
+        let year: i32 = match parts.next() {
+            Some(y) if y.len() == 4 => y.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+        let (interval, month) = match parts.next() {
+            None => (Interval::Year, 1),
+            Some(q) if q.starts_with('Q') || q.starts_with('q') => match q[1..].parse::<u32>() {
+                Ok(n @ 1..=4) => (Interval::Quarter, (n - 1) * 3 + 1),
+                _ => return Err(invalid()),
+            },
+            Some(m) if m.len() == 2 => (Interval::Month, m.parse().map_err(|_| invalid())?),
+            Some(_) => return Err(invalid()),
+        };
+        let start = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(invalid)?;
+
+        Ok(whole(interval, &start))
+    }
+
+    /// Retrieve the first day covered by the expression
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Retrieve the day after the last day covered by the expression
+    pub fn end(&self) -> NaiveDate {
+        self.end
+    }
+
+    /// Check if the expression names a whole period, instead of a single day
+    pub fn is_period(&self) -> bool {
+        self.period
+    }
+
+    /// Retrieve the date to use as an upper bound that is excluded.
+    ///
+    /// This is the day after a period, so that the whole period is included, or the day itself.
+    pub fn end_excluded(&self) -> NaiveDate {
+        if self.period {
+            self.end
+        } else {
+            self.start
+        }
+    }
+
+    /// Retrieve the date to use as an upper bound that is included.
+    ///
+    /// This is the last day of a period, so that the whole period is included, or the day itself.
+    pub fn end_included(&self) -> NaiveDate {
+        if self.period {
+            self.end.pred_opt().unwrap_or(self.start)
+        } else {
+            self.start
+        }
+    }
+}
+
+impl FromStr for DateExpr {
+    type Err = IntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, &TODAY)
+    }
+}
+
+/// Parse a date expression into the first day it covers, for options like `--date-from`.
+pub fn parse_date(s: &str) -> Result<NaiveDate, IntervalError> {
+    DateExpr::from_str(s).map(|expr| expr.start())
+}
+
+/// Parse a date expression into an upper bound that is excluded, for options like `--date-to`.
+pub fn parse_date_to(s: &str) -> Result<NaiveDate, IntervalError> {
+    DateExpr::from_str(s).map(|expr| expr.end_excluded())
+}
+
+/// Parse a date expression into an upper bound that is included, for options like `--until`.
+pub fn parse_date_until(s: &str) -> Result<NaiveDate, IntervalError> {
+    DateExpr::from_str(s).map(|expr| expr.end_included())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[track_caller]
+    fn check_parse(input: &str, expected: Result<(NaiveDate, NaiveDate), IntervalError>) {
+        // a Wednesday
+        let today = ymd(2026, 5, 13);
+        let observed = DateExpr::parse(input, &today).map(|expr| (expr.start(), expr.end()));
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn parse_days() {
+        check_parse("today", Ok((ymd(2026, 5, 13), ymd(2026, 5, 14))));
+        check_parse("yesterday", Ok((ymd(2026, 5, 12), ymd(2026, 5, 13))));
+        check_parse("2026-03-14", Ok((ymd(2026, 3, 14), ymd(2026, 3, 15))));
+        check_parse("-30d", Ok((ymd(2026, 4, 13), ymd(2026, 5, 14))));
+        check_parse("-2w", Ok((ymd(2026, 4, 29), ymd(2026, 5, 14))));
+        check_parse("-1y", Ok((ymd(2025, 5, 13), ymd(2026, 5, 14))));
+    }
+
+    #[test]
+    fn parse_periods() {
+        check_parse("2026", Ok((ymd(2026, 1, 1), ymd(2027, 1, 1))));
+        check_parse("2026-Q2", Ok((ymd(2026, 4, 1), ymd(2026, 7, 1))));
+        check_parse("2026-03", Ok((ymd(2026, 3, 1), ymd(2026, 4, 1))));
+        check_parse("this-quarter", Ok((ymd(2026, 4, 1), ymd(2026, 7, 1))));
+        check_parse("last-month", Ok((ymd(2026, 4, 1), ymd(2026, 5, 1))));
+        check_parse("last-year", Ok((ymd(2025, 1, 1), ymd(2026, 1, 1))));
+        check_parse("next-week", Ok((ymd(2026, 5, 18), ymd(2026, 5, 25))));
+        check_parse("ytd", Ok((ymd(2026, 1, 1), ymd(2026, 5, 14))));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in [
+            "",
+            "-",
+            "-30x",
+            "last-fortnight",
+            "2026-Q5",
+            "2026-13",
+            "26-03",
+        ] {
+            check_parse(input, Err(IntervalError::InvalidDate(input.to_string())));
+        }
+    }
+
+    #[test]
+    fn bounds() {
+        let today = ymd(2026, 5, 13);
+        let month = DateExpr::parse("2026-03", &today).unwrap();
+        let day = DateExpr::parse("2026-03-14", &today).unwrap();
+
+        assert_eq!(ymd(2026, 4, 1), month.end_excluded());
+        assert_eq!(ymd(2026, 3, 31), month.end_included());
+        assert_eq!(ymd(2026, 3, 14), day.end_excluded());
+        assert_eq!(ymd(2026, 3, 14), day.end_included());
+    }
+}
@@ -0,0 +1,15 @@
+//! Errors when parsing an [`Interval`][crate::interval::interval_struct::Interval] or a [`DateExpr`][crate::interval::interval_date::DateExpr].
+
+use thiserror::Error;
+
+/// Errors when parsing an [`Interval`][crate::interval::interval_struct::Interval] or a [`DateExpr`][crate::interval::interval_date::DateExpr].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IntervalError {
+    /// When the interval isn't one of the supported lengths of time.
+    #[error("Invalid interval `{0}`. Must be 'day', 'week', 'month', 'quarter', or 'year'.")]
+    InvalidInterval(String),
+
+    /// When a date isn't a date, a relative date, or a period.
+    #[error("Invalid date `{0}`. Must be a date like '2026-03-14', a period like '2026-Q2' or '2026-03', 'today', 'yesterday', a relative date like '-30d', 'this-month', 'last-quarter', or 'ytd'.")]
+    InvalidDate(String),
+}
@@ -0,0 +1,98 @@
+//! A span of time, broken down into periods of an [`Interval`][crate::interval::interval_struct::Interval].
+
+use super::{parse_date, parse_date_to, DateExpr, Interval};
+use crate::category::FIRST_OF_NEXT_MONTH;
+use chrono::{Months, NaiveDate};
+use clap::Parser;
+
+/// A span of time, broken down into periods of an [`Interval`], for reports over time.
+#[derive(Debug, Parser)]
+pub struct ReportPeriods {
+    /// Start from the period including this date.
+    /// Defaults to a year before `--to`.
+    #[clap(
+        short = 'd',
+        long = "from",
+        visible_alias = "date-from",
+        parse(try_from_str = parse_date),
+        allow_hyphen_values = true,
+        value_name = "date"
+    )]
+    date_from: Option<NaiveDate>,
+
+    /// Stop at the period up to and excluding this date.
+    /// Defaults to the start of next month.
+    #[clap(
+        short = 'D',
+        long = "to",
+        visible_alias = "date-to",
+        parse(try_from_str = parse_date_to),
+        allow_hyphen_values = true,
+        value_name = "date"
+    )]
+    date_to: Option<NaiveDate>,
+
+    /// Report on the periods in this span of time, instead of `--from` and `--to`.
+    /// Use a period like '2026', '2026-Q2', 'last-year', or 'ytd', or a relative date like '-90d' for the days since then.
+    #[clap(
+        long = "period",
+        conflicts_with_all = &["date-from", "date-to"],
+        allow_hyphen_values = true,
+        value_name = "period"
+    )]
+    period: Option<DateExpr>,
+
+    /// Length of each period. Options are 'day', 'week', 'month', 'quarter', or 'year'.
+    #[clap(
+        short = 'i',
+        long = "interval",
+        default_value = "month",
+        value_name = "interval"
+    )]
+    interval: Interval,
+}
+
+impl ReportPeriods {
+    /// Create a new span of time for a report
+    pub fn new(
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+        interval: Interval,
+    ) -> Self {
+        Self {
+            date_from,
+            date_to,
+            period: None,
+            interval,
+        }
+    }
+
+    /// Retrieve the earliest date that is included
+    pub fn date_from(&self) -> NaiveDate {
+        match self.period.map(|period| period.start()).or(self.date_from) {
+            Some(d) => d,
+            None => self
+                .date_to()
+                .checked_sub_months(Months::new(12))
+                .unwrap_or(NaiveDate::MIN),
+        }
+    }
+
+    /// Retrieve the date that everything before is included
+    pub fn date_to(&self) -> NaiveDate {
+        match self.period {
+            Some(period) => period.end(),
+            None => self.date_to.unwrap_or(*FIRST_OF_NEXT_MONTH),
+        }
+    }
+
+    /// Retrieve the length of each period
+    pub fn interval(&self) -> &Interval {
+        &self.interval
+    }
+
+    /// Split the span of time into periods, each given by its first day and the day after its last day.
+    pub fn periods(&self) -> Vec<(NaiveDate, NaiveDate)> {
+        self.interval.periods(&self.date_from(), &self.date_to())
+    }
+}
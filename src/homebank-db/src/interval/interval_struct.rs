@@ -0,0 +1,160 @@
+//! Intervals of time that reports are broken down into.
+
+use super::IntervalError;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use std::{fmt, str::FromStr};
+
+/// Length of each period that a report is broken down into.
+///
+/// Periods follow the calendar, so months start on the 1st, weeks start on Monday, and quarters start in January, April, July, and October.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Interval {
+    Day,
+    Week,
+    #[default]
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Interval {
+    /// Find the first day of the period that includes `date`.
+    pub fn start_of(&self, date: &NaiveDate) -> NaiveDate {
+        match self {
+            Interval::Day => *date,
+            Interval::Week => *date - Duration::days(date.weekday().num_days_from_monday().into()),
+            Interval::Month => date.with_day(1).unwrap(),
+            Interval::Quarter => {
+                NaiveDate::from_ymd_opt(date.year(), (date.month0() / 3) * 3 + 1, 1).unwrap()
+            }
+            Interval::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap(),
+        }
+    }
+
+    /// Find the first day of the period after the one that includes `date`.
+    pub fn next_start(&self, date: &NaiveDate) -> NaiveDate {
+        let start = self.start_of(date);
+        let next = match self {
+            Interval::Day => start.checked_add_signed(Duration::days(1)),
+            Interval::Week => start.checked_add_signed(Duration::weeks(1)),
+            Interval::Month => start.checked_add_months(Months::new(1)),
+            Interval::Quarter => start.checked_add_months(Months::new(3)),
+            Interval::Year => start.checked_add_months(Months::new(12)),
+        };
+
+        next.unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Split the time from `from` up to and excluding `to` into periods.
+    ///
+    /// Each period is given by its first day and the day after its last day.
+    /// The first and last periods are cut short if `from` or `to` don't fall on the start of a period.
+    pub fn periods(&self, from: &NaiveDate, to: &NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+        let mut periods = vec![];
+        let mut start = *from;
+        while start < *to {
+            let end = self.next_start(&start).min(*to);
+            periods.push((start, end));
+            start = end;
+        }
+
+        periods
+    }
+
+    /// Name the period that starts on `start`, such as `2021-03` for a month or `2021-Q1` for a quarter.
+    pub fn label(&self, start: &NaiveDate) -> String {
+        match self {
+            Interval::Day | Interval::Week => start.format("%Y-%m-%d").to_string(),
+            Interval::Month => start.format("%Y-%m").to_string(),
+            Interval::Quarter => format!("{}-Q{}", start.year(), start.month0() / 3 + 1),
+            Interval::Year => start.format("%Y").to_string(),
+        }
+    }
+}
+
+impl FromStr for Interval {
+    type Err = IntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Day" | "day" | "daily" | "d" => Ok(Interval::Day),
+            "Week" | "week" | "weekly" | "w" => Ok(Interval::Week),
+            "Month" | "month" | "monthly" | "m" => Ok(Interval::Month),
+            "Quarter" | "quarter" | "quarterly" | "q" => Ok(Interval::Quarter),
+            "Year" | "year" | "yearly" | "y" => Ok(Interval::Year),
+            _ => Err(IntervalError::InvalidInterval(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Interval::Day => write!(f, "day"),
+            Interval::Week => write!(f, "week"),
+            Interval::Month => write!(f, "month"),
+            Interval::Quarter => write!(f, "quarter"),
+            Interval::Year => write!(f, "year"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[track_caller]
+    fn check_start_of(interval: Interval, date: NaiveDate, expected: NaiveDate) {
+        assert_eq!(expected, interval.start_of(&date));
+    }
+
+    #[test]
+    fn start_of_each_interval() {
+        // 2021-03-17 is a Wednesday
+        check_start_of(Interval::Day, ymd(2021, 3, 17), ymd(2021, 3, 17));
+        check_start_of(Interval::Week, ymd(2021, 3, 17), ymd(2021, 3, 15));
+        check_start_of(Interval::Month, ymd(2021, 3, 17), ymd(2021, 3, 1));
+        check_start_of(Interval::Quarter, ymd(2021, 3, 17), ymd(2021, 1, 1));
+        check_start_of(Interval::Quarter, ymd(2021, 11, 2), ymd(2021, 10, 1));
+        check_start_of(Interval::Year, ymd(2021, 3, 17), ymd(2021, 1, 1));
+    }
+
+    #[test]
+    fn monthly_periods() {
+        let observed = Interval::Month.periods(&ymd(2021, 1, 15), &ymd(2021, 3, 10));
+        let expected = vec![
+            (ymd(2021, 1, 15), ymd(2021, 2, 1)),
+            (ymd(2021, 2, 1), ymd(2021, 3, 1)),
+            (ymd(2021, 3, 1), ymd(2021, 3, 10)),
+        ];
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn no_periods_when_empty() {
+        assert!(Interval::Year
+            .periods(&ymd(2021, 1, 1), &ymd(2021, 1, 1))
+            .is_empty());
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!("2021-03", Interval::Month.label(&ymd(2021, 3, 1)));
+        assert_eq!("2021-Q4", Interval::Quarter.label(&ymd(2021, 10, 1)));
+        assert_eq!("2021", Interval::Year.label(&ymd(2021, 1, 1)));
+    }
+
+    #[test]
+    fn parse_interval() {
+        assert_eq!(Ok(Interval::Quarter), Interval::from_str("quarterly"));
+        assert_eq!(
+            Err(IntervalError::InvalidInterval("fortnight".to_string())),
+            Interval::from_str("fortnight")
+        );
+    }
+}
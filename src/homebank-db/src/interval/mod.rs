@@ -0,0 +1,11 @@
+//! Intervals of time that reports are broken down into.
+
+pub mod interval_date;
+pub mod interval_error;
+pub mod interval_periods;
+pub mod interval_struct;
+
+pub use interval_date::{parse_date, parse_date_to, parse_date_until, DateExpr};
+pub use interval_error::IntervalError;
+pub use interval_periods::ReportPeriods;
+pub use interval_struct::Interval;
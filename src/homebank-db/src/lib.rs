@@ -1,27 +1,37 @@
 //! Library implementation of HomeBank that is heavily inspired by [HomeBank's C implementation](https://code.launchpad.net/~mdoyen/homebank/).
 
 pub mod account;
+pub mod assignment;
 pub mod category;
 pub mod currency;
 pub mod db;
+mod flags;
 pub mod group;
+pub mod interval;
+pub mod money;
 pub mod payee;
 pub mod paymode;
 pub mod query;
-// pub mod template;
+pub mod tag;
+pub mod template;
 pub mod transaction;
 
-pub use account::{Account, AccountError, AccountType, QueryAccounts};
-pub use category::{Category, CategoryError, QueryCategories};
+pub use account::{Account, AccountError, AccountFlags, AccountType, QueryAccounts};
+pub use assignment::{Assignment, AssignmentError, QueryAssignments};
+pub use category::{Category, CategoryError, CategoryFlags, QueryCategories};
 pub use currency::{Currency, CurrencyError, QueryCurrencies};
-pub use db::{HomeBankDb, HomeBankDbProperties, HomeBankDbSchema};
+pub use db::{HomeBankDb, HomeBankDbProperties, HomeBankDbSchema, ParseDiagnostic};
 pub use group::{Group, QueryGroups};
+pub use interval::{DateExpr, Interval, IntervalError};
+pub use money::{Money, MoneyError};
 pub use payee::{Payee, PayeeError, QueryPayees};
 pub use paymode::PayMode;
 pub use query::{Query, QueryOpts, QueryType};
-// pub use template::{QueryTemplates, Template};
+pub use tag::{QueryTags, Tag, TagError};
+pub use template::{QueryTemplates, Template, TemplateError};
 pub use transaction::{
-    QueryTransactions, Transaction, TransactionError, TransactionStatus, TransactionType,
+    QueryTransactions, Transaction, TransactionError, TransactionFlags, TransactionSort,
+    TransactionStatus, TransactionType,
 };
 
 #[cfg(test)]
@@ -0,0 +1,7 @@
+//! Exact amounts of money, as stored in a HomeBank database.
+
+pub mod money_error;
+pub mod money_struct;
+
+pub use money_error::MoneyError;
+pub use money_struct::Money;
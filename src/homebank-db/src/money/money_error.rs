@@ -0,0 +1,11 @@
+//! Errors when parsing an amount of [`Money`][crate::money::money_struct::Money].
+
+use thiserror::Error;
+
+/// Errors when parsing an amount of [`Money`][crate::money::money_struct::Money].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MoneyError {
+    /// When the amount is not a decimal number, or has more digits than can be stored exactly.
+    #[error("Invalid amount `{0}`.")]
+    InvalidAmount(String),
+}
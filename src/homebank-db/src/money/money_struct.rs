@@ -0,0 +1,261 @@
+//! Exact amounts of money, as stored in a HomeBank database.
+
+use super::MoneyError;
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// An exact amount of money.
+///
+/// HomeBank stores amounts as the shortest decimal string of a `double`, such as `339.01999999999998`.
+/// These are kept as exact decimals so that adding up thousands of them never drifts,
+/// and so that writing them back out reproduces the same string.
+/// Amounts are only rounded to a [`Currency`'s][crate::currency::currency_struct::Currency] number of decimals when they are displayed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(Decimal);
+
+impl Money {
+    /// No money at all.
+    pub const ZERO: Money = Money(Decimal::ZERO);
+
+    /// Create an amount from a whole number of the smallest unit and the number of decimals in that unit.
+    ///
+    /// For example, `Money::new(1250, 2)` is `12.50`.
+    pub fn new(num: i64, scale: u32) -> Self {
+        Self(Decimal::new(num, scale))
+    }
+
+    /// Retrieve the amount as a decimal number.
+    pub fn as_decimal(&self) -> Decimal {
+        self.0
+    }
+
+    /// Determine if the amount is exactly zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Determine if the amount is strictly less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.0 < Decimal::ZERO
+    }
+
+    /// Determine if the amount is strictly greater than zero.
+    pub fn is_positive(&self) -> bool {
+        self.0 > Decimal::ZERO
+    }
+
+    /// Retrieve the amount without its sign.
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Round the amount to a number of decimals, with halves rounded away from zero like HomeBank does.
+    pub fn round(&self, decimal_len: usize) -> Self {
+        Self(
+            self.0
+                .round_dp_with_strategy(decimal_len as u32, RoundingStrategy::MidpointAwayFromZero),
+        )
+    }
+
+    /// Multiply the amount by an exchange rate, returning `None` if the result is too large.
+    pub fn checked_mul(&self, rate: Decimal) -> Option<Self> {
+        self.0.checked_mul(rate).map(Self)
+    }
+
+    /// Divide the amount by an exchange rate, returning `None` if the rate is zero or the result is too large.
+    pub fn checked_div(&self, rate: Decimal) -> Option<Self> {
+        self.0.checked_div(rate).map(Self)
+    }
+
+    /// Retrieve an approximation of the amount, for ratios and other non-monetary calculations.
+    pub fn to_f32(&self) -> f32 {
+        self.0.to_f32().unwrap_or(0.0)
+    }
+
+    /// Retrieve an approximation of the amount, for ratios and other non-monetary calculations.
+    pub fn to_f64(&self) -> f64 {
+        self.0.to_f64().unwrap_or(0.0)
+    }
+}
+
+impl From<i64> for Money {
+    fn from(num: i64) -> Self {
+        Self(Decimal::from(num))
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(d: Decimal) -> Self {
+        Self(d)
+    }
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+
+        // very small or very large amounts are written in scientific notation
+        let parsed = if trimmed.contains(['e', 'E']) {
+            Decimal::from_scientific(trimmed)
+        } else {
+            Decimal::from_str(trimmed)
+        };
+
+        match parsed {
+            Ok(d) => Ok(Self(d)),
+            Err(_) => Err(MoneyError::InvalidAmount(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    /// Display the amount exactly, or rounded if a precision is given (e.g. `{:.2}`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(dp) => {
+                let rounded = self.round(dp);
+                fmt::Display::fmt(&rounded.0, f)
+            }
+            None => fmt::Display::fmt(&self.0, f),
+        }
+    }
+}
+
+impl Add for Money {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Money {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |a, b| a + b)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |a, &b| a + b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_from_str(input: &str, expected: Result<Money, MoneyError>) {
+        let observed = Money::from_str(input);
+
+        assert_eq!(expected, observed);
+    }
+
+    #[track_caller]
+    fn check_display(input: Money, precision: Option<usize>, expected: &str) {
+        let observed = match precision {
+            Some(dp) => format!("{input:.dp$}"),
+            None => format!("{input}"),
+        };
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn parse_whole() {
+        check_from_str("42", Ok(Money::from(42)));
+        check_from_str("-42", Ok(Money::from(-42)));
+    }
+
+    #[test]
+    fn parse_fraction() {
+        check_from_str("-12.99", Ok(Money::new(-1299, 2)));
+        check_from_str("339.01999999999998", Ok(Money::new(33901999999999998, 14)));
+    }
+
+    #[test]
+    fn parse_scientific() {
+        check_from_str("1e-05", Ok(Money::new(1, 5)));
+        check_from_str("-2.5E3", Ok(Money::from(-2500)));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        check_from_str("", Err(MoneyError::InvalidAmount("".to_string())));
+        check_from_str("abc", Err(MoneyError::InvalidAmount("abc".to_string())));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        check_display(Money::new(33901999999999998, 14), None, "339.01999999999998");
+        check_display(Money::new(-1250, 2), None, "-12.50");
+    }
+
+    #[test]
+    fn display_rounded() {
+        check_display(Money::new(33901999999999998, 14), Some(2), "339.02");
+        check_display(Money::from(5), Some(2), "5.00");
+        check_display(Money::new(125, 3), Some(2), "0.13");
+        check_display(Money::new(-125, 3), Some(2), "-0.13");
+        check_display(Money::new(-12345, 1), Some(0), "-1235");
+    }
+
+    #[test]
+    fn equal_regardless_of_scale() {
+        assert_eq!(Money::new(1250, 2), Money::new(125, 1));
+    }
+
+    #[test]
+    fn convert_with_rate() {
+        let rate = Decimal::new(125, 2);
+
+        assert_eq!(Some(Money::new(1250, 2)), Money::from(10).checked_mul(rate));
+        assert_eq!(Some(Money::from(8)), Money::from(10).checked_div(rate));
+        assert_eq!(None, Money::from(10).checked_div(Decimal::ZERO));
+    }
+
+    #[test]
+    fn sum_does_not_drift() {
+        // 0.1 cannot be stored exactly as a float, so adding it up many times drifts
+        let amounts: Vec<Money> = (0..100_000).map(|_| Money::new(1, 1)).collect();
+        let observed: Money = amounts.iter().sum();
+
+        assert_eq!(Money::from(10_000), observed);
+    }
+}
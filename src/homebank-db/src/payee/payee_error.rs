@@ -3,7 +3,7 @@
 use thiserror::Error;
 
 /// Errors when parsing [`Payee`s][crate::payee::payee_struct::Payee] from the [`HomeBankDb`][crate::db::db_struct::HomeBankDb].
-#[derive(Debug, Error)]
+#[derive(Debug, Error, PartialEq)]
 pub enum PayeeError {
     /// When the key for the payee is invalid.
     #[error("Invalid payee key.")]
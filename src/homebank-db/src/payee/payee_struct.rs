@@ -1,6 +1,7 @@
 //! The donor or recipient of a [`Transaction`][crate::transaction::transaction_struct::Transaction].
 
 use super::PayeeError;
+use crate::db::db_writer::xml_attribute;
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
 
@@ -103,3 +104,21 @@ impl TryFrom<Vec<OwnedAttribute>> for Payee {
         Ok(payee)
     }
 }
+
+impl From<&Payee> for Vec<OwnedAttribute> {
+    fn from(payee: &Payee) -> Self {
+        let mut v = vec![
+            xml_attribute("key", payee.key),
+            xml_attribute("name", &payee.name),
+        ];
+
+        if let Some(cat) = payee.default_category_key {
+            v.push(xml_attribute("category", cat));
+        }
+        if let Some(pm) = payee.default_paymode_key {
+            v.push(xml_attribute("paymode", pm));
+        }
+
+        v
+    }
+}
@@ -1,11 +1,12 @@
 //! Payment method for a [`Transaction`][crate::transaction::transaction_struct::Transaction].
 
 use crate::TransactionError;
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 /// Payment method for a [`Transaction`][crate::transaction::transaction_struct::Transaction].
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub enum PayMode {
+    #[default]
     None,
     CreditCard,
     Cheque,
@@ -19,12 +20,6 @@ pub enum PayMode {
     DirectDebit,
 }
 
-impl Default for PayMode {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl TryFrom<usize> for PayMode {
     type Error = TransactionError;
 
@@ -46,6 +41,43 @@ impl TryFrom<usize> for PayMode {
     }
 }
 
+impl From<PayMode> for usize {
+    fn from(pm: PayMode) -> Self {
+        match pm {
+            PayMode::None => 0,
+            PayMode::CreditCard => 1,
+            PayMode::Cheque => 2,
+            PayMode::Cash => 3,
+            PayMode::BankTransfer => 4,
+            PayMode::DebitCard => 5,
+            PayMode::StandingOrder => 6,
+            PayMode::ElectronicPayment => 7,
+            PayMode::Deposit => 8,
+            PayMode::FinancialInstitutionFee => 9,
+            PayMode::DirectDebit => 10,
+        }
+    }
+}
+
+impl fmt::Display for PayMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PayMode::None => "none",
+            PayMode::CreditCard => "credit_card",
+            PayMode::Cheque => "cheque",
+            PayMode::Cash => "cash",
+            PayMode::BankTransfer => "bank_transfer",
+            PayMode::DebitCard => "debit_card",
+            PayMode::StandingOrder => "standing_order",
+            PayMode::ElectronicPayment => "electronic_payment",
+            PayMode::Deposit => "deposit",
+            PayMode::FinancialInstitutionFee => "fee",
+            PayMode::DirectDebit => "direct_debit",
+        };
+        write!(f, "{name}")
+    }
+}
+
 impl FromStr for PayMode {
     type Err = TransactionError;
 
@@ -1,8 +1,8 @@
 //! Query the HomeBank database from the command line.
 
 use crate::{
-    currency::QueryCurrencies, group::QueryGroups, payee::QueryPayees,
-    transaction::QueryTransactions, HomeBankDb, QueryAccounts, QueryCategories,
+    assignment::QueryAssignments, currency::QueryCurrencies, group::QueryGroups, payee::QueryPayees,
+    tag::QueryTags, transaction::QueryTransactions, HomeBankDb, QueryAccounts, QueryCategories, QueryTemplates,
 };
 use clap::Parser;
 
@@ -29,13 +29,17 @@ impl QueryOpts {
 }
 
 /// Differentiate between the different query types from the CLI
+// the transaction query holds many more options than the others, but only one query is parsed at a time
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Parser)]
 pub enum QueryType {
     Accounts(QueryAccounts),
+    Assignments(QueryAssignments),
     Categories(QueryCategories),
     Currencies(QueryCurrencies),
     Groups(QueryGroups),
     Payees(QueryPayees),
-    // Templates(QueryTemplates),
+    Tags(QueryTags),
+    Templates(QueryTemplates),
     Transactions(QueryTransactions),
 }
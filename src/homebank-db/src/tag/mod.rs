@@ -0,0 +1,9 @@
+//! User-provided tags that label [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+
+pub mod tag_error;
+pub mod tag_query;
+pub mod tag_struct;
+
+pub use tag_error::TagError;
+pub use tag_query::{QueryTags, TagSummary};
+pub use tag_struct::Tag;
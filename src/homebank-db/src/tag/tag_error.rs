@@ -0,0 +1,11 @@
+//! Errors when parsing [`Tag`s][crate::tag::tag_struct::Tag] from the HomeBank XML file.
+
+use thiserror::Error;
+
+/// Errors when parsing [`Tag`s][crate::tag::tag_struct::Tag] from the HomeBank XML file.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TagError {
+    /// When the key for the tag is invalid.
+    #[error("Invalid tag key.")]
+    InvalidKey,
+}
@@ -0,0 +1,210 @@
+//! Options for filtering [`Tag`s][crate::tag::tag_struct::Tag] from the [`HomeBankDb`].
+
+use crate::{HomeBankDb, Money, Query};
+use clap::Parser;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Options for filtering [`Tag`s][crate::tag::tag_struct::Tag] from the [`HomeBankDb`].
+#[derive(Debug, Parser)]
+#[clap(
+    name = "tags",
+    visible_alias = "x",
+    about = "Query transaction tags and how often they are used"
+)]
+pub struct QueryTags {
+    /// Name of the tag.
+    #[clap(value_name = "regex")]
+    name: Option<Regex>,
+
+    /// Only include tags used on transactions that are missing from the tag list.
+    #[clap(short = 'm', long = "missing")]
+    missing: bool,
+
+    /// Only include tags in the tag list that no transaction uses.
+    #[clap(short = 'u', long = "unused")]
+    unused: bool,
+}
+
+impl QueryTags {
+    /// Retrieve the regular expression for the tag name
+    fn name(&self) -> &Option<Regex> {
+        &self.name
+    }
+
+    /// Retrieve whether only tags missing from the tag list are included
+    fn missing(&self) -> bool {
+        self.missing
+    }
+
+    /// Retrieve whether only unused tags are included
+    fn unused(&self) -> bool {
+        self.unused
+    }
+}
+
+/// How often a tag is used across all [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+#[derive(Debug, PartialEq, Clone)]
+pub struct TagSummary {
+    /// Name of the tag.
+    name: String,
+
+    /// Key of the [`Tag`][crate::tag::tag_struct::Tag] in the database, if it is in the tag list.
+    key: Option<usize>,
+
+    /// Number of transactions with this tag.
+    count: usize,
+
+    /// Sum of all transactions with this tag.
+    total: Money,
+}
+
+impl TagSummary {
+    /// Create a new tag summary
+    pub fn new(name: &str, key: Option<usize>, count: usize, total: Money) -> Self {
+        Self {
+            name: name.to_string(),
+            key,
+            count,
+            total,
+        }
+    }
+
+    /// Retrieve the name of the tag
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieve the key of the tag in the database, if it is in the tag list
+    pub fn key(&self) -> Option<usize> {
+        self.key
+    }
+
+    /// Retrieve the number of transactions with this tag
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Retrieve the sum of all transactions with this tag
+    pub fn total(&self) -> Money {
+        self.total
+    }
+
+    /// Check if the tag is used on transactions but missing from the tag list
+    pub fn is_missing(&self) -> bool {
+        self.key.is_none()
+    }
+
+    /// Check if the tag is in the tag list but no transaction uses it
+    pub fn is_unused(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl Query for QueryTags {
+    type T = TagSummary;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        // keep the tags sorted by name
+        let mut summaries: BTreeMap<String, TagSummary> = db
+            .tags()
+            .values()
+            .map(|tag| {
+                (
+                    tag.name().to_string(),
+                    TagSummary::new(tag.name(), Some(tag.key()), 0, Money::ZERO),
+                )
+            })
+            .collect();
+
+        for tr in db.transactions() {
+            if let Some(tags) = tr.tags() {
+                for tag in tags {
+                    let summary = summaries
+                        .entry(tag.to_string())
+                        .or_insert_with(|| TagSummary::new(tag, None, 0, Money::ZERO));
+                    summary.count += 1;
+                    summary.total += *tr.total();
+                }
+            }
+        }
+
+        summaries
+            .into_values()
+            // filter out tags that don't match the regex
+            .filter(|summary| match self.name() {
+                Some(re) => re.is_match(summary.name()),
+                None => true,
+            })
+            // filter out tags that are properly used, if only problems are requested
+            .filter(|summary| {
+                if !self.missing() && !self.unused() {
+                    true
+                } else {
+                    (self.missing() && summary.is_missing()) || (self.unused() && summary.is_unused())
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_exec(query: QueryTags, expected: Vec<TagSummary>) {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<tag key="1" name="vacation"/>
+<tag key="2" name="gift"/>
+<ope date="737495" amount="-10" account="1" tags="vacation"/>
+<ope date="737495" amount="-20" account="1" tags="vacation reimbursable"/>
+<ope date="737495" amount="-5" account="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+
+        assert_eq!(expected, query.exec(&db));
+    }
+
+    #[test]
+    fn summarize_all_tags() {
+        let query = QueryTags {
+            name: None,
+            missing: false,
+            unused: false,
+        };
+        let expected = vec![
+            TagSummary::new("gift", Some(2), 0, Money::ZERO),
+            TagSummary::new("reimbursable", None, 1, Money::from(-20)),
+            TagSummary::new("vacation", Some(1), 2, Money::from(-30)),
+        ];
+
+        check_exec(query, expected);
+    }
+
+    #[test]
+    fn missing_tags() {
+        let query = QueryTags {
+            name: None,
+            missing: true,
+            unused: false,
+        };
+        let expected = vec![TagSummary::new("reimbursable", None, 1, Money::from(-20))];
+
+        check_exec(query, expected);
+    }
+
+    #[test]
+    fn unused_tags() {
+        let query = QueryTags {
+            name: None,
+            missing: false,
+            unused: true,
+        };
+        let expected = vec![TagSummary::new("gift", Some(2), 0, Money::ZERO)];
+
+        check_exec(query, expected);
+    }
+}
@@ -0,0 +1,83 @@
+//! User-provided tags that label [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+
+use super::TagError;
+use crate::db::db_writer::xml_attribute;
+use std::str::FromStr;
+use xml::attribute::OwnedAttribute;
+
+/// User-provided tags that label [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tag {
+    /// Unique key for the tag in the database.
+    key: usize,
+
+    /// Name of the tag, as it appears on transactions.
+    name: String,
+}
+
+impl Tag {
+    /// Create the empty, default `Tag`
+    pub fn empty() -> Self {
+        Self {
+            key: 0,
+            name: "".to_string(),
+        }
+    }
+
+    /// Create a new `Tag`
+    pub fn new(key: usize, name: &str) -> Self {
+        Self {
+            key,
+            name: name.to_string(),
+        }
+    }
+
+    /// Retrieve the key for the `Tag`
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Retrieve the name of the `Tag`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Default for Tag {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl TryFrom<Vec<OwnedAttribute>> for Tag {
+    type Error = TagError;
+
+    fn try_from(v: Vec<OwnedAttribute>) -> Result<Self, Self::Error> {
+        let mut tag = Self::default();
+
+        for i in v {
+            match i.name.local_name.as_str() {
+                "key" => {
+                    tag.key = match usize::from_str(&i.value) {
+                        Ok(idx) => idx,
+                        Err(_) => return Err(TagError::InvalidKey),
+                    }
+                }
+                "name" => {
+                    tag.name = i.value.as_str().to_string();
+                }
+                _ => {}
+            }
+        }
+        Ok(tag)
+    }
+}
+
+impl From<&Tag> for Vec<OwnedAttribute> {
+    fn from(tag: &Tag) -> Self {
+        vec![
+            xml_attribute("key", tag.key),
+            xml_attribute("name", &tag.name),
+        ]
+    }
+}
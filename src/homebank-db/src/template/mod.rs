@@ -1,5 +1,13 @@
-pub mod template;
+//! Favourite and scheduled [`Transaction`s][crate::transaction::transaction_struct::Transaction] that act as templates for new ones.
+
+pub mod template_error;
 pub mod template_query;
+pub mod template_schedule;
+pub mod template_struct;
+pub mod upcoming_query;
 
-pub use template::Template;
+pub use template_error::TemplateError;
 pub use template_query::QueryTemplates;
+pub use template_schedule::{ScheduleUnit, WeekendRule};
+pub use template_struct::Template;
+pub use upcoming_query::{QueryUpcoming, UpcomingTransaction};
@@ -0,0 +1,40 @@
+//! Errors when parsing [`Template`s][crate::template::template_struct::Template] from the HomeBank XML file.
+
+use crate::TransactionError;
+use thiserror::Error;
+
+/// Errors when parsing [`Template`s][crate::template::template_struct::Template] from the HomeBank XML file.
+#[derive(Debug, Error, PartialEq)]
+pub enum TemplateError {
+    /// When the key for the template is invalid.
+    #[error("Invalid template key.")]
+    InvalidKey,
+
+    /// When the next scheduled date is invalid.
+    #[error("Invalid next date for the scheduled transaction.")]
+    InvalidNextDate,
+
+    /// When the number of units between scheduled transactions is invalid.
+    #[error("Invalid schedule frequency. Must be a positive integer.")]
+    InvalidEvery,
+
+    /// When the unit of time between scheduled transactions is invalid.
+    #[error("Invalid schedule unit. Must be 0-3 or 'day', 'week', 'month', or 'year'.")]
+    InvalidUnit,
+
+    /// When the number of remaining scheduled transactions is invalid.
+    #[error("Invalid schedule limit. Must be of type `u32`.")]
+    InvalidLimit,
+
+    /// When the rule for scheduled transactions falling on a weekend is invalid.
+    #[error("Invalid weekend rule. Must be 0-3 or 'possible', 'before', 'after', or 'skip'.")]
+    InvalidWeekend,
+
+    /// When a transfer's destination account is invalid.
+    #[error("Invalid destination account identifier from template. Must be of type `usize` and cannot be 0.")]
+    InvalidDestinationAccount,
+
+    /// When the rest of the template cannot be parsed as a transaction.
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+}
@@ -1,9 +1,88 @@
+//! Options for filtering [`Template`s][crate::template::template_struct::Template] from the [`HomeBankDb`].
+
+use crate::{HomeBankDb, Query, Template};
 use clap::Parser;
+use regex::Regex;
 
+/// Options for filtering [`Template`s][crate::template::template_struct::Template] from the [`HomeBankDb`].
 #[derive(Debug, Parser)]
 #[clap(
     name = "templates",
     visible_alias = "T",
     about = "Query templates and scheduled transactions"
 )]
-pub struct QueryTemplates {}
+pub struct QueryTemplates {
+    /// Name of the template.
+    #[clap(value_name = "regex")]
+    name: Option<Regex>,
+
+    /// Include templates whose payees match the regular expression.
+    #[clap(short = 'p', long = "payee", value_name = "regex")]
+    payee: Option<Regex>,
+
+    /// Include templates whose accounts match the regular expression.
+    #[clap(short = 'a', long = "account", value_name = "regex")]
+    account: Option<Regex>,
+
+    /// Only include scheduled transactions, not plain favourites.
+    #[clap(short = 's', long = "scheduled")]
+    scheduled: bool,
+}
+
+impl QueryTemplates {
+    /// Retrieve the regular expression for the template name
+    fn name(&self) -> &Option<Regex> {
+        &self.name
+    }
+
+    /// Retrieve the regular expression for the template payee
+    fn payee(&self) -> &Option<Regex> {
+        &self.payee
+    }
+
+    /// Retrieve the regular expression for the template account
+    fn account(&self) -> &Option<Regex> {
+        &self.account
+    }
+
+    /// Retrieve whether only scheduled transactions are included
+    fn scheduled(&self) -> bool {
+        self.scheduled
+    }
+}
+
+impl Query for QueryTemplates {
+    type T = Template;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let mut filt_templates: Vec<Template> = db
+            .templates()
+            .values()
+            // filter out favourites if only scheduled transactions are requested
+            .filter(|&tmpl| !self.scheduled() || tmpl.is_scheduled())
+            // filter out templates that don't match the name regex
+            .filter(|&tmpl| match self.name() {
+                Some(re) => re.is_match(tmpl.name()),
+                None => true,
+            })
+            // filter out templates that don't match the payee regex
+            .filter(|&tmpl| match (self.payee(), tmpl.payee_name(db)) {
+                (Some(re), Some(payee)) => re.is_match(&payee),
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            // filter out templates that don't match the account regex
+            .filter(|&tmpl| match (self.account(), tmpl.account_name(db)) {
+                (Some(re), Some(acct)) => re.is_match(&acct),
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .cloned()
+            .collect();
+
+        // list the templates in the order they will next occur
+        filt_templates.sort_by_key(|tmpl| (*tmpl.next_date(), tmpl.key()));
+
+        filt_templates
+    }
+}
@@ -0,0 +1,237 @@
+//! How often a scheduled [`Template`][crate::template::template_struct::Template] recurs, and what happens when it falls on a weekend.
+
+use super::TemplateError;
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::{fmt, str::FromStr};
+
+/// Unit of time between each scheduled [`Transaction`][crate::transaction::transaction_struct::Transaction].
+///
+/// HomeBank leaves out a unit of `Day` when saving, so that is the default.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum ScheduleUnit {
+    #[default]
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl ScheduleUnit {
+    /// Move a date forward by a number of these units.
+    ///
+    /// Like HomeBank, adding months or years to the end of a month is clamped to the end of the resulting month
+    /// (e.g. January 31 + 1 month is February 28).
+    pub fn advance(&self, date: &NaiveDate, every: u32) -> NaiveDate {
+        let next = match self {
+            ScheduleUnit::Day => date.checked_add_signed(Duration::days(every.into())),
+            ScheduleUnit::Week => date.checked_add_signed(Duration::weeks(every.into())),
+            ScheduleUnit::Month => date.checked_add_months(Months::new(every)),
+            ScheduleUnit::Year => date.checked_add_months(Months::new(every.saturating_mul(12))),
+        };
+
+        next.unwrap_or(NaiveDate::MAX)
+    }
+}
+
+impl TryFrom<usize> for ScheduleUnit {
+    type Error = TemplateError;
+
+    fn try_from(u: usize) -> Result<Self, Self::Error> {
+        match u {
+            0 => Ok(ScheduleUnit::Day),
+            1 => Ok(ScheduleUnit::Week),
+            2 => Ok(ScheduleUnit::Month),
+            3 => Ok(ScheduleUnit::Year),
+            _ => Err(TemplateError::InvalidUnit),
+        }
+    }
+}
+
+impl From<ScheduleUnit> for usize {
+    fn from(unit: ScheduleUnit) -> Self {
+        match unit {
+            ScheduleUnit::Day => 0,
+            ScheduleUnit::Week => 1,
+            ScheduleUnit::Month => 2,
+            ScheduleUnit::Year => 3,
+        }
+    }
+}
+
+impl FromStr for ScheduleUnit {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" | "Day" | "day" | "d" => Ok(ScheduleUnit::Day),
+            "1" | "Week" | "week" | "w" => Ok(ScheduleUnit::Week),
+            "2" | "Month" | "month" | "m" => Ok(ScheduleUnit::Month),
+            "3" | "Year" | "year" | "y" => Ok(ScheduleUnit::Year),
+            _ => Err(TemplateError::InvalidUnit),
+        }
+    }
+}
+
+impl fmt::Display for ScheduleUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleUnit::Day => write!(f, "day"),
+            ScheduleUnit::Week => write!(f, "week"),
+            ScheduleUnit::Month => write!(f, "month"),
+            ScheduleUnit::Year => write!(f, "year"),
+        }
+    }
+}
+
+/// What to do with a scheduled [`Transaction`][crate::transaction::transaction_struct::Transaction] that falls on a weekend.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum WeekendRule {
+    /// Keep the date as is.
+    #[default]
+    Possible,
+
+    /// Move the date to the Friday before.
+    Before,
+
+    /// Move the date to the Monday after.
+    After,
+
+    /// Skip this occurrence entirely.
+    Skip,
+}
+
+impl WeekendRule {
+    /// Apply the rule to a scheduled date.
+    ///
+    /// Returns `None` if the occurrence should be skipped.
+    pub fn apply(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let days_past_friday = match date.weekday() {
+            Weekday::Sat => 1,
+            Weekday::Sun => 2,
+            _ => return Some(*date),
+        };
+
+        match self {
+            WeekendRule::Possible => Some(*date),
+            WeekendRule::Before => Some(*date - Duration::days(days_past_friday)),
+            WeekendRule::After => Some(*date + Duration::days(3 - days_past_friday)),
+            WeekendRule::Skip => None,
+        }
+    }
+}
+
+impl TryFrom<usize> for WeekendRule {
+    type Error = TemplateError;
+
+    fn try_from(u: usize) -> Result<Self, Self::Error> {
+        match u {
+            0 => Ok(WeekendRule::Possible),
+            1 => Ok(WeekendRule::Before),
+            2 => Ok(WeekendRule::After),
+            3 => Ok(WeekendRule::Skip),
+            _ => Err(TemplateError::InvalidWeekend),
+        }
+    }
+}
+
+impl From<WeekendRule> for usize {
+    fn from(rule: WeekendRule) -> Self {
+        match rule {
+            WeekendRule::Possible => 0,
+            WeekendRule::Before => 1,
+            WeekendRule::After => 2,
+            WeekendRule::Skip => 3,
+        }
+    }
+}
+
+impl FromStr for WeekendRule {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" | "Possible" | "possible" => Ok(WeekendRule::Possible),
+            "1" | "Before" | "before" => Ok(WeekendRule::Before),
+            "2" | "After" | "after" => Ok(WeekendRule::After),
+            "3" | "Skip" | "skip" => Ok(WeekendRule::Skip),
+            _ => Err(TemplateError::InvalidWeekend),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_advance(unit: ScheduleUnit, date: (i32, u32, u32), every: u32, expected: (i32, u32, u32)) {
+        let date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+        let expected = NaiveDate::from_ymd_opt(expected.0, expected.1, expected.2).unwrap();
+
+        assert_eq!(expected, unit.advance(&date, every));
+    }
+
+    #[track_caller]
+    fn check_weekend(rule: WeekendRule, date: (i32, u32, u32), expected: Option<(i32, u32, u32)>) {
+        let date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+        let expected = expected.map(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        assert_eq!(expected, rule.apply(&date));
+    }
+
+    #[test]
+    fn advance_days() {
+        check_advance(ScheduleUnit::Day, (2023, 2, 27), 3, (2023, 3, 2));
+    }
+
+    #[test]
+    fn advance_weeks() {
+        check_advance(ScheduleUnit::Week, (2023, 2, 27), 2, (2023, 3, 13));
+    }
+
+    #[test]
+    fn advance_months() {
+        check_advance(ScheduleUnit::Month, (2023, 1, 15), 1, (2023, 2, 15));
+        check_advance(ScheduleUnit::Month, (2023, 11, 15), 3, (2024, 2, 15));
+    }
+
+    #[test]
+    fn advance_months_clamps_to_end_of_month() {
+        check_advance(ScheduleUnit::Month, (2023, 1, 31), 1, (2023, 2, 28));
+    }
+
+    #[test]
+    fn advance_years() {
+        check_advance(ScheduleUnit::Year, (2024, 2, 29), 1, (2025, 2, 28));
+    }
+
+    #[test]
+    fn weekday_is_never_moved() {
+        // 2023-03-01 is a Wednesday
+        check_weekend(WeekendRule::Before, (2023, 3, 1), Some((2023, 3, 1)));
+        check_weekend(WeekendRule::After, (2023, 3, 1), Some((2023, 3, 1)));
+        check_weekend(WeekendRule::Skip, (2023, 3, 1), Some((2023, 3, 1)));
+    }
+
+    #[test]
+    fn weekend_possible() {
+        check_weekend(WeekendRule::Possible, (2023, 3, 4), Some((2023, 3, 4)));
+    }
+
+    #[test]
+    fn weekend_before() {
+        check_weekend(WeekendRule::Before, (2023, 3, 4), Some((2023, 3, 3)));
+        check_weekend(WeekendRule::Before, (2023, 3, 5), Some((2023, 3, 3)));
+    }
+
+    #[test]
+    fn weekend_after() {
+        check_weekend(WeekendRule::After, (2023, 3, 4), Some((2023, 3, 6)));
+        check_weekend(WeekendRule::After, (2023, 3, 5), Some((2023, 3, 6)));
+    }
+
+    #[test]
+    fn weekend_skip() {
+        check_weekend(WeekendRule::Skip, (2023, 3, 5), None);
+    }
+}
@@ -0,0 +1,519 @@
+//! Favourite and scheduled [`Transaction`s][crate::transaction::transaction_struct::Transaction] that act as templates for new ones.
+
+use super::{ScheduleUnit, TemplateError, WeekendRule};
+use crate::{
+    db::db_writer::xml_attribute,
+    transaction::{julian_date_from_u32, julian_date_to_u32, Transfer},
+    HomeBankDb, Transaction, TransactionFlags, TransactionType,
+};
+use chrono::NaiveDate;
+use std::str::FromStr;
+use xml::attribute::OwnedAttribute;
+
+/// A favourite or scheduled [`Transaction`] that acts as a template for new ones.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Template {
+    /// Unique key for the template in the database.
+    key: usize,
+
+    /// The transaction that will be created from this template.
+    /// Its date is the next date the transaction is scheduled for.
+    transaction: Transaction,
+
+    /// Number of [`unit`s][Template::unit] between each scheduled transaction.
+    every: u32,
+
+    /// Unit of time between each scheduled transaction.
+    unit: ScheduleUnit,
+
+    /// Number of scheduled transactions remaining, if the schedule is limited.
+    limit: u32,
+
+    /// What to do when a scheduled transaction falls on a weekend.
+    weekend: WeekendRule,
+}
+
+impl Template {
+    /// Create an empty [`Template`].
+    pub fn empty() -> Self {
+        Self {
+            key: 0,
+            transaction: Transaction::empty(),
+            every: 1,
+            unit: ScheduleUnit::default(),
+            limit: 0,
+            weekend: WeekendRule::default(),
+        }
+    }
+
+    /// Create a new [`Template`].
+    pub fn new(
+        key: usize,
+        transaction: &Transaction,
+        every: u32,
+        unit: ScheduleUnit,
+        limit: u32,
+        weekend: WeekendRule,
+    ) -> Self {
+        Self {
+            key,
+            transaction: transaction.clone(),
+            every,
+            unit,
+            limit,
+            weekend,
+        }
+    }
+
+    /// Retrieve the template's key from the database.
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Retrieve the name of the template, which HomeBank takes from the memo.
+    pub fn name(&self) -> &str {
+        match self.transaction.memo() {
+            Some(memo) => memo,
+            None => "",
+        }
+    }
+
+    /// Retrieve the [`Transaction`] that will be created from this template.
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Retrieve the [`Payee`'s][crate::payee::payee_struct::Payee] name.
+    pub fn payee_name(&self, db: &HomeBankDb) -> Option<String> {
+        self.transaction.payee_name(db)
+    }
+
+    /// Retrieve the [`Account`][crate::account::account_struct::Account] name.
+    pub fn account_name(&self, db: &HomeBankDb) -> Option<String> {
+        self.transaction.account_name(db)
+    }
+
+    /// Check if the template is a scheduled transaction or only a favourite.
+    pub fn is_scheduled(&self) -> bool {
+        self.flags().contains(TransactionFlags::AUTO)
+    }
+
+    /// Retrieve the next date the transaction is scheduled for, before any [`WeekendRule`] is applied.
+    pub fn next_date(&self) -> &NaiveDate {
+        self.transaction.date()
+    }
+
+    /// Retrieve the number of [`unit`s][Template::unit] between each scheduled transaction.
+    pub fn every(&self) -> u32 {
+        self.every
+    }
+
+    /// Retrieve the unit of time between each scheduled transaction.
+    pub fn unit(&self) -> &ScheduleUnit {
+        &self.unit
+    }
+
+    /// Retrieve the number of scheduled transactions remaining, if the schedule is limited.
+    pub fn limit(&self) -> Option<u32> {
+        if self.flags().contains(TransactionFlags::LIMIT) {
+            Some(self.limit)
+        } else {
+            None
+        }
+    }
+
+    /// Retrieve what happens when a scheduled transaction falls on a weekend.
+    pub fn weekend(&self) -> &WeekendRule {
+        &self.weekend
+    }
+
+    /// Find the scheduled date that follows `date`.
+    pub fn date_after(&self, date: &NaiveDate) -> NaiveDate {
+        self.unit.advance(date, self.every)
+    }
+
+    /// Find every date the transaction is scheduled for, up to and including `until`.
+    ///
+    /// Dates falling on a weekend are moved or skipped according to the [`WeekendRule`],
+    /// and no more dates than the [`limit`][Template::limit] are returned.
+    /// Favourites that aren't scheduled never occur.
+    pub fn occurrences_until(&self, until: &NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = vec![];
+        if !self.is_scheduled() {
+            return dates;
+        }
+
+        let mut remaining = self.limit();
+        let mut date = *self.next_date();
+        while date <= *until && remaining != Some(0) {
+            match self.weekend.apply(&date) {
+                // moved past the end, so it isn't due yet
+                Some(d) if d > *until => break,
+                Some(d) => dates.push(d),
+                None => {}
+            }
+            // skipped dates still count towards the limit, like in HomeBank
+            remaining = remaining.map(|n| n - 1);
+            date = self.date_after(&date);
+        }
+
+        dates
+    }
+
+    /// Move the next scheduled date past every occurrence up to and including `until`.
+    ///
+    /// This is the counterpart of [`occurrences_until`][Template::occurrences_until], for once those occurrences have been posted.
+    /// When a limited schedule runs out, the template stops being scheduled, like in HomeBank.
+    pub fn advance_past(&mut self, until: &NaiveDate) {
+        if !self.is_scheduled() {
+            return;
+        }
+
+        let mut date = *self.next_date();
+        while date <= *until && self.limit() != Some(0) {
+            if matches!(self.weekend.apply(&date), Some(d) if d > *until) {
+                break;
+            }
+            if self.limit().is_some() {
+                self.limit -= 1;
+            }
+            date = self.date_after(&date);
+        }
+        *self.transaction.mut_date() = date;
+
+        if self.limit() == Some(0) {
+            let mut flags = self.flags();
+            flags.remove(TransactionFlags::AUTO | TransactionFlags::LIMIT);
+            *self.transaction.mut_flags() = Some(flags);
+        }
+    }
+
+    /// Create the [`Transaction`s][crate::transaction::transaction_struct::Transaction] for an occurrence of this template on a given date.
+    ///
+    /// Transfers create a transaction in both accounts, linked by `transfer_key`.
+    pub fn transactions_on(&self, date: &NaiveDate, transfer_key: usize) -> Vec<Transaction> {
+        let mut tr = self.transaction.clone();
+        *tr.mut_date() = *date;
+        // the scheduling flags only apply to the template
+        if let Some(flags) = tr.mut_flags() {
+            flags.remove(TransactionFlags::AUTO | TransactionFlags::LIMIT);
+        }
+        if let TransactionType::Transfer(xfer) = tr.mut_ttype() {
+            *xfer.mut_transfer_key() = transfer_key;
+        }
+
+        match tr.transfer_counterpart() {
+            Some(counterpart) => vec![tr, counterpart],
+            None => vec![tr],
+        }
+    }
+
+    /// Retrieve the flags on the template.
+    fn flags(&self) -> TransactionFlags {
+        self.transaction.flags().unwrap_or_default()
+    }
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl TryFrom<Vec<OwnedAttribute>> for Template {
+    type Error = TemplateError;
+
+    fn try_from(v: Vec<OwnedAttribute>) -> Result<Self, Self::Error> {
+        let mut tmpl = Self::default();
+        let mut next_date: Option<NaiveDate> = None;
+        let mut destination: Option<usize> = None;
+        // everything that isn't specific to templates is parsed as a transaction
+        let mut tr_atts = vec![];
+
+        for i in v {
+            match i.name.local_name.as_str() {
+                "key" => {
+                    tmpl.key = match usize::from_str(&i.value) {
+                        Ok(idx) => idx,
+                        Err(_) => return Err(TemplateError::InvalidKey),
+                    }
+                }
+                "nextdate" => {
+                    next_date = match u32::from_str(&i.value) {
+                        Ok(d) => Some(julian_date_from_u32(d)),
+                        Err(_) => return Err(TemplateError::InvalidNextDate),
+                    }
+                }
+                "every" => {
+                    tmpl.every = match u32::from_str(&i.value) {
+                        Ok(e) if e > 0 => e,
+                        _ => return Err(TemplateError::InvalidEvery),
+                    }
+                }
+                "unit" => {
+                    tmpl.unit = match usize::from_str(&i.value) {
+                        Ok(u) => ScheduleUnit::try_from(u)?,
+                        Err(_) => return Err(TemplateError::InvalidUnit),
+                    }
+                }
+                "limit" => {
+                    tmpl.limit = match u32::from_str(&i.value) {
+                        Ok(l) => l,
+                        Err(_) => return Err(TemplateError::InvalidLimit),
+                    }
+                }
+                "weekend" => {
+                    tmpl.weekend = match usize::from_str(&i.value) {
+                        Ok(w) => WeekendRule::try_from(w)?,
+                        Err(_) => return Err(TemplateError::InvalidWeekend),
+                    }
+                }
+                // templates for transfers have a destination, but no transfer key until they are posted
+                "dst_account" => {
+                    destination = match usize::from_str(&i.value) {
+                        Ok(acct_idx) if acct_idx > 0 => Some(acct_idx),
+                        _ => return Err(TemplateError::InvalidDestinationAccount),
+                    }
+                }
+                "date" | "kxfer" => {}
+                _ => tr_atts.push(i),
+            }
+        }
+
+        tmpl.transaction = Transaction::try_from(tr_atts)?;
+        if let Some(d) = next_date {
+            *tmpl.transaction.mut_date() = d;
+        }
+        if let Some(acct_idx) = destination {
+            *tmpl.transaction.mut_ttype() = TransactionType::Transfer(Transfer::new(0, acct_idx));
+        }
+
+        Ok(tmpl)
+    }
+}
+
+impl From<&Template> for Vec<OwnedAttribute> {
+    fn from(tmpl: &Template) -> Self {
+        let mut v = vec![xml_attribute("key", tmpl.key)];
+
+        // templates don't have a date or transfer key of their own
+        let tr_atts: Vec<OwnedAttribute> = tmpl.transaction().into();
+        v.extend(
+            tr_atts
+                .into_iter()
+                .filter(|att| !matches!(att.name.local_name.as_str(), "date" | "kxfer")),
+        );
+
+        v.push(xml_attribute("nextdate", julian_date_to_u32(tmpl.next_date())));
+        v.push(xml_attribute("every", tmpl.every));
+        // HomeBank leaves out a unit, limit, or weekend rule of 0
+        if tmpl.unit != ScheduleUnit::Day {
+            v.push(xml_attribute("unit", usize::from(tmpl.unit)));
+        }
+        if tmpl.limit != 0 {
+            v.push(xml_attribute("limit", tmpl.limit));
+        }
+        if tmpl.weekend != WeekendRule::Possible {
+            v.push(xml_attribute("weekend", usize::from(tmpl.weekend)));
+        }
+
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Money, TransactionError};
+    use xml::{reader::XmlEvent, EventReader};
+
+    /// Parse the first element of an XML string into a [`Template`].
+    #[track_caller]
+    fn parse_first_element(input: &str) -> Result<Template, TemplateError> {
+        let mut reader = EventReader::from_str(input);
+        let (_start, first) = (reader.next(), reader.next());
+
+        if let Ok(XmlEvent::StartElement { attributes, .. }) = first {
+            Template::try_from(attributes)
+        } else {
+            panic!("Incorrect string passed into check. `{:#?}`", first);
+        }
+    }
+
+    #[test]
+    fn parse_scheduled_template() {
+        let input = r#"<fav key="3" amount="-12.99" account="1" paymode="6" st="0" flags="132" payee="2" category="5" wording="Streaming" nextdate="737836" every="1" unit="2" limit="4" weekend="2"/>"#;
+        let observed = parse_first_element(input).unwrap();
+
+        assert_eq!(3, observed.key());
+        assert_eq!("Streaming", observed.name());
+        assert!(observed.is_scheduled());
+        assert_eq!(&NaiveDate::from_ymd_opt(2021, 2, 15).unwrap(), observed.next_date());
+        assert_eq!(1, observed.every());
+        assert_eq!(&ScheduleUnit::Month, observed.unit());
+        assert_eq!(Some(4), observed.limit());
+        assert_eq!(&WeekendRule::After, observed.weekend());
+        assert_eq!(&Money::new(-1299, 2), observed.transaction().total());
+    }
+
+    #[test]
+    fn parse_favourite() {
+        let input = r#"<fav key="30" amount="0" paymode="1" st="1" flags="512" payee="35" category="104" nextdate="737836" every="1" unit="2"/>"#;
+        let observed = parse_first_element(input).unwrap();
+
+        assert!(!observed.is_scheduled());
+        assert_eq!(None, observed.limit());
+        assert_eq!("", observed.name());
+    }
+
+    #[test]
+    fn parse_transfer_template() {
+        let input = r#"<fav key="1" amount="-100" account="1" dst_account="2" paymode="4" flags="4" nextdate="737836" every="2" unit="1"/>"#;
+        let observed = parse_first_element(input).unwrap();
+
+        assert!(observed.transaction().is_transfer());
+        assert_eq!(Some(&2), observed.transaction().transfer_destination());
+        assert_eq!(&ScheduleUnit::Week, observed.unit());
+    }
+
+    #[test]
+    fn parse_invalid_unit() {
+        let input = r#"<fav key="1" amount="-1" unit="7"/>"#;
+
+        assert_eq!(Err(TemplateError::InvalidUnit), parse_first_element(input));
+    }
+
+    #[test]
+    fn parse_invalid_every() {
+        let input = r#"<fav key="1" amount="-1" every="0"/>"#;
+
+        assert_eq!(Err(TemplateError::InvalidEvery), parse_first_element(input));
+    }
+
+    #[test]
+    fn parse_invalid_transaction() {
+        let input = r#"<fav key="1" amount="x"/>"#;
+
+        assert_eq!(
+            Err(TemplateError::Transaction(TransactionError::InvalidAmount)),
+            parse_first_element(input)
+        );
+    }
+
+    #[track_caller]
+    fn check_occurrences(input: &str, until: (i32, u32, u32), expected: Vec<(i32, u32, u32)>) {
+        let tmpl = parse_first_element(input).unwrap();
+        let until = NaiveDate::from_ymd_opt(until.0, until.1, until.2).unwrap();
+        let expected: Vec<NaiveDate> = expected
+            .iter()
+            .map(|&(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap())
+            .collect();
+
+        assert_eq!(expected, tmpl.occurrences_until(&until));
+    }
+
+    #[test]
+    fn occurrences_monthly() {
+        // next date is 2021-02-15
+        let input = r#"<fav key="1" amount="-1" flags="4" nextdate="737836" every="1" unit="2"/>"#;
+
+        check_occurrences(input, (2021, 2, 14), vec![]);
+        check_occurrences(
+            input,
+            (2021, 4, 15),
+            vec![(2021, 2, 15), (2021, 3, 15), (2021, 4, 15)],
+        );
+    }
+
+    #[test]
+    fn occurrences_limited() {
+        let input = r#"<fav key="1" amount="-1" flags="132" nextdate="737836" every="2" unit="1" limit="2"/>"#;
+
+        check_occurrences(input, (2021, 12, 31), vec![(2021, 2, 15), (2021, 3, 1)]);
+    }
+
+    #[test]
+    fn occurrences_on_weekend() {
+        // 2021-02-20 is a Saturday
+        let input = r#"<fav key="1" amount="-1" flags="4" nextdate="737841" every="1" unit="0" weekend="3"/>"#;
+
+        check_occurrences(input, (2021, 2, 22), vec![(2021, 2, 22)]);
+    }
+
+    #[test]
+    fn favourites_never_occur() {
+        let input = r#"<fav key="1" amount="-1" nextdate="737836" every="1" unit="2"/>"#;
+
+        check_occurrences(input, (2021, 12, 31), vec![]);
+    }
+
+    #[test]
+    fn transfer_transactions_on() {
+        let input = r#"<fav key="1" amount="-100" account="1" dst_account="2" flags="4" nextdate="737836"/>"#;
+        let tmpl = parse_first_element(input).unwrap();
+        let date = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let observed = tmpl.transactions_on(&date, 7);
+
+        assert_eq!(2, observed.len());
+        assert_eq!(&date, observed[0].date());
+        assert_eq!(&Some(TransactionFlags::empty()), observed[0].flags());
+        assert_eq!(Some(&7), observed[0].transfer_key());
+        assert_eq!(2, observed[1].account());
+        assert_eq!(&Money::from(100), observed[1].total());
+    }
+
+    #[test]
+    fn occurrence_moved_past_until() {
+        // 2021-02-20 is a Saturday, which moves to the Monday after
+        let input = r#"<fav key="1" amount="-1" flags="4" nextdate="737841" every="1" unit="0" weekend="2"/>"#;
+
+        check_occurrences(input, (2021, 2, 21), vec![]);
+    }
+
+    #[test]
+    fn advance_past_occurrences() {
+        let input = r#"<fav key="1" amount="-1" flags="4" nextdate="737836" every="1" unit="2"/>"#;
+        let mut tmpl = parse_first_element(input).unwrap();
+        tmpl.advance_past(&NaiveDate::from_ymd_opt(2021, 3, 31).unwrap());
+
+        assert_eq!(&NaiveDate::from_ymd_opt(2021, 4, 15).unwrap(), tmpl.next_date());
+        assert!(tmpl.is_scheduled());
+    }
+
+    #[test]
+    fn advance_past_limit() {
+        let input = r#"<fav key="1" amount="-1" flags="132" nextdate="737836" every="1" unit="2" limit="3"/>"#;
+        let mut tmpl = parse_first_element(input).unwrap();
+        tmpl.advance_past(&NaiveDate::from_ymd_opt(2021, 3, 31).unwrap());
+
+        assert_eq!(Some(1), tmpl.limit());
+
+        tmpl.advance_past(&NaiveDate::from_ymd_opt(2021, 12, 31).unwrap());
+
+        assert!(!tmpl.is_scheduled());
+        assert!(tmpl
+            .occurrences_until(&NaiveDate::from_ymd_opt(2021, 12, 31).unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn round_trip_template() {
+        let input = r#"<fav key="1" amount="-100" account="1" dst_account="2" paymode="4" flags="132" payee="2" wording="Savings" tags="auto" nextdate="737836" every="2" unit="1" limit="3" weekend="1"/>"#;
+        let original = parse_first_element(input).unwrap();
+        let written: Vec<OwnedAttribute> = (&original).into();
+
+        assert_eq!(Ok(original), Template::try_from(written));
+    }
+
+    #[test]
+    fn round_trip_daily_template() {
+        let input = r#"<fav key="1" amount="-5" account="1" flags="4" wording="Coffee" nextdate="737836" every="1" unit="0"/>"#;
+        let original = parse_first_element(input).unwrap();
+        let written: Vec<OwnedAttribute> = (&original).into();
+
+        // HomeBank leaves out a daily unit, so it has to be read back as daily
+        assert_eq!(&ScheduleUnit::Day, original.unit());
+        assert!(!written.iter().any(|att| att.name.local_name == "unit"));
+        assert_eq!(Ok(original), Template::try_from(written));
+    }
+}
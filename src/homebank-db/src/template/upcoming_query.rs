@@ -0,0 +1,168 @@
+//! Forecast the scheduled [`Transaction`s][crate::transaction::transaction_struct::Transaction] coming up in the [`HomeBankDb`].
+
+use crate::{category::TODAY, interval::parse_date_until, HomeBankDb, Money, Query, Transaction};
+use chrono::NaiveDate;
+use clap::Parser;
+use std::collections::HashMap;
+
+/// Forecast the scheduled [`Transaction`s][crate::transaction::transaction_struct::Transaction] coming up in the [`HomeBankDb`].
+#[derive(Debug, Parser)]
+pub struct QueryUpcoming {
+    /// Include scheduled transactions up to and including this date.
+    /// Defaults to how far ahead HomeBank is set to add scheduled transactions.
+    #[clap(
+        short = 'u',
+        long = "until",
+        parse(try_from_str = parse_date_until),
+        allow_hyphen_values = true,
+        value_name = "date"
+    )]
+    until: Option<NaiveDate>,
+}
+
+impl QueryUpcoming {
+    /// Create a new query for upcoming scheduled transactions
+    pub fn new(until: Option<NaiveDate>) -> Self {
+        Self { until }
+    }
+
+    /// Retrieve the latest date that scheduled transactions are included up to
+    fn until(&self, db: &HomeBankDb) -> NaiveDate {
+        match self.until {
+            Some(d) => d,
+            None => db.properties().sched_mode().post_limit(&TODAY),
+        }
+    }
+}
+
+/// A single occurrence of a scheduled [`Template`][crate::template::template_struct::Template] in one [`Account`][crate::account::account_struct::Account].
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpcomingTransaction {
+    /// Key of the [`Template`][crate::template::template_struct::Template] that the transaction comes from.
+    template_key: usize,
+
+    /// The transaction, dated to when it will occur.
+    transaction: Transaction,
+
+    /// Projected balance of the [`Account`][crate::account::account_struct::Account] after the transaction.
+    balance: Money,
+}
+
+impl UpcomingTransaction {
+    /// Create a new upcoming transaction
+    pub fn new(template_key: usize, transaction: &Transaction, balance: Money) -> Self {
+        Self {
+            template_key,
+            transaction: transaction.clone(),
+            balance,
+        }
+    }
+
+    /// Retrieve the key of the [`Template`][crate::template::template_struct::Template] the transaction comes from
+    pub fn template_key(&self) -> usize {
+        self.template_key
+    }
+
+    /// Retrieve the upcoming transaction
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Retrieve the projected balance of the account after the transaction
+    pub fn balance(&self) -> Money {
+        self.balance
+    }
+}
+
+impl Query for QueryUpcoming {
+    type T = UpcomingTransaction;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let until = self.until(db);
+
+        // expand every scheduled template into the transactions it creates
+        let mut upcoming: Vec<(usize, Transaction)> = db
+            .templates()
+            .values()
+            .flat_map(|tmpl| {
+                tmpl.occurrences_until(&until)
+                    .iter()
+                    .flat_map(|date| tmpl.transactions_on(date, 0))
+                    .map(|tr| (tmpl.key(), tr))
+                    .collect::<Vec<(usize, Transaction)>>()
+            })
+            .collect();
+        upcoming.sort_by_key(|(key, tr)| (*tr.date(), *key, tr.account()));
+
+        // start from the balance of each account, leaving out void transactions and reminders
+        let mut balances: HashMap<usize, Money> = db
+            .accounts()
+            .iter()
+            .map(|(&key, acct)| (key, acct.future_balance(db)))
+            .collect();
+
+        upcoming
+            .iter()
+            .map(|(key, tr)| {
+                let balance = balances.entry(tr.account()).or_insert(Money::ZERO);
+                *balance += *tr.total();
+
+                UpcomingTransaction::new(*key, tr, *balance)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upcoming_in_date_order_with_balances() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing" initial="100"/>
+<account key="2" name="Savings" initial="0"/>
+<fav key="1" amount="-10" account="1" flags="4" wording="Streaming" nextdate="737836" every="1" unit="2"/>
+<fav key="2" amount="-50" account="1" dst_account="2" flags="4" wording="Savings" nextdate="737830" every="2" unit="1"/>
+<fav key="3" amount="-99" account="1" wording="Not scheduled" nextdate="737830"/>
+<ope date="737800" amount="20" account="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryUpcoming::new(NaiveDate::from_ymd_opt(2021, 2, 28));
+        let observed: Vec<(NaiveDate, usize, Money)> = query
+            .exec(&db)
+            .iter()
+            .map(|up| (*up.transaction().date(), up.transaction().account(), up.balance()))
+            .collect();
+        let expected = vec![
+            // 2021-02-09 is when the first transfer happens
+            (NaiveDate::from_ymd_opt(2021, 2, 9).unwrap(), 1, Money::from(70)),
+            (NaiveDate::from_ymd_opt(2021, 2, 9).unwrap(), 2, Money::from(50)),
+            (NaiveDate::from_ymd_opt(2021, 2, 15).unwrap(), 1, Money::from(60)),
+            (NaiveDate::from_ymd_opt(2021, 2, 23).unwrap(), 1, Money::from(10)),
+            (NaiveDate::from_ymd_opt(2021, 2, 23).unwrap(), 2, Money::from(100)),
+        ];
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn upcoming_balances_skip_void_and_remind() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing" initial="100"/>
+<fav key="1" amount="-10" account="1" flags="4" wording="Streaming" nextdate="737836" every="1" unit="2"/>
+<ope date="737800" amount="20" account="1"/>
+<ope date="737801" amount="-500" account="1" st="3"/>
+<ope date="737802" amount="-70" account="1" st="4"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryUpcoming::new(NaiveDate::from_ymd_opt(2021, 2, 20));
+        let observed: Vec<Money> = query.exec(&db).iter().map(|up| up.balance()).collect();
+
+        assert_eq!(vec![Money::from(110)], observed);
+    }
+}
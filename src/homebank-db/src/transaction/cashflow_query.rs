@@ -0,0 +1,198 @@
+//! Income and expenses over time in the [`HomeBankDb`].
+
+use super::sum_transactions_in;
+use crate::{interval::ReportPeriods, HomeBankDb, Interval, Money, Query};
+use chrono::NaiveDate;
+use clap::Parser;
+
+/// Query the income, expenses, and savings rate of each period.
+///
+/// Transfers between accounts are left out, since they are neither income nor expenses.
+#[derive(Debug, Parser)]
+pub struct QueryCashflow {
+    /// The periods to report the cash flow for.
+    #[clap(flatten)]
+    periods: ReportPeriods,
+}
+
+impl QueryCashflow {
+    /// Create a new query for cash flow over time
+    pub fn new(
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+        interval: Interval,
+    ) -> Self {
+        Self {
+            periods: ReportPeriods::new(date_from, date_to, interval),
+        }
+    }
+
+    /// Retrieve the periods to report the cash flow for
+    fn periods(&self) -> &ReportPeriods {
+        &self.periods
+    }
+}
+
+/// The income and expenses of a single period, in the base currency.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cashflow {
+    /// Name of the period, such as `2021-03`.
+    label: String,
+
+    /// Total income over the period.
+    income: Money,
+
+    /// Total expenses over the period, as a negative amount.
+    expense: Money,
+
+    /// Whether any of the amounts were converted from another currency.
+    converted: bool,
+}
+
+impl Cashflow {
+    /// Create a new cash flow for a period
+    pub fn new(label: &str, income: Money, expense: Money, converted: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            income,
+            expense,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the period
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Retrieve the total income over the period
+    pub fn income(&self) -> Money {
+        self.income
+    }
+
+    /// Retrieve the total expenses over the period, as a negative amount
+    pub fn expense(&self) -> Money {
+        self.expense
+    }
+
+    /// Retrieve the income left over after the expenses
+    pub fn net(&self) -> Money {
+        self.income + self.expense
+    }
+
+    /// Retrieve the percentage of income left over after the expenses, if there was any income
+    pub fn savings_rate(&self) -> Option<f64> {
+        if self.income.is_positive() {
+            Some(100.0 * self.net().to_f64() / self.income.to_f64())
+        } else {
+            None
+        }
+    }
+
+    /// Check if any of the amounts were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+impl Query for QueryCashflow {
+    type T = Cashflow;
+
+    fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
+        let base = db.properties().currency();
+        let interval = self.periods().interval();
+
+        self.periods()
+            .periods()
+            .iter()
+            .map(|(start, end)| {
+                // accounts can be left out of reports, like in HomeBank
+                let (income, expense): (Vec<_>, Vec<_>) = db
+                    .transactions()
+                    .iter()
+                    .filter(|tr| tr.date() >= start && tr.date() < end)
+                    .filter(|tr| tr.counts_towards_balance() && !tr.is_transfer())
+                    .filter(|tr| !tr.is_excluded_from_reports(db))
+                    // split transactions can have both income and expense lines, so classify each line
+                    .flat_map(|tr| match tr.num_splits() {
+                        0 => vec![tr.clone()],
+                        n => (0..n).filter_map(|i| tr.subset(&[i])).collect(),
+                    })
+                    .partition(|tr| tr.total().is_positive());
+
+                let (income_sum, income_converted) = sum_transactions_in(&income, db, base);
+                let (expense_sum, expense_converted) = sum_transactions_in(&expense, db, base);
+
+                Cashflow::new(
+                    &interval.label(start),
+                    income_sum,
+                    expense_sum,
+                    income_converted || expense_converted,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monthly_cashflow_without_transfers() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<account key="2" name="Savings"/>
+<ope date="737791" amount="1000" account="1"/>
+<ope date="737792" amount="-250" account="1"/>
+<ope date="737793" amount="-500" account="1" dst_account="2" kxfer="1"/>
+<ope date="737793" amount="500" account="2" dst_account="1" kxfer="1"/>
+<ope date="737794" amount="-99" account="1" st="4"/>
+<ope date="737830" amount="-100" account="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryCashflow::new(
+            NaiveDate::from_ymd_opt(2021, 1, 1),
+            NaiveDate::from_ymd_opt(2021, 3, 1),
+            Interval::Month,
+        );
+        let observed = query.exec(&db);
+        let expected = vec![
+            Cashflow::new("2021-01", Money::from(1000), Money::from(-250), false),
+            Cashflow::new("2021-02", Money::ZERO, Money::from(-100), false),
+        ];
+
+        assert_eq!(expected, observed);
+        assert_eq!(Money::from(750), observed[0].net());
+        assert_eq!(Some(75.0), observed[0].savings_rate());
+        assert_eq!(None, observed[1].savings_rate());
+    }
+
+    #[test]
+    fn split_lines_classified_separately() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<account key="1" name="Chequing"/>
+<cat key="1" name="Salary"/>
+<cat key="2" name="Taxes"/>
+<ope date="737791" amount="70" account="1" flags="256" scat="1||2" samt="100||-30" smem="||"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let query = QueryCashflow::new(
+            NaiveDate::from_ymd_opt(2021, 1, 1),
+            NaiveDate::from_ymd_opt(2021, 2, 1),
+            Interval::Month,
+        );
+        let expected = vec![Cashflow::new(
+            "2021-01",
+            Money::from(100),
+            Money::from(-30),
+            false,
+        )];
+
+        assert_eq!(expected, query.exec(&db));
+    }
+}
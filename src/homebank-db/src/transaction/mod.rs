@@ -1,10 +1,14 @@
 //! Individual transactions applied to one or more [`Account`s][crate::account::account_struct::Account].
 
+pub mod cashflow_query;
 pub mod transaction_complexity;
 pub mod transaction_date;
 pub mod transaction_error;
+pub mod transaction_flags;
+pub mod transaction_group;
 pub mod transaction_query;
 pub mod transaction_simple;
+pub mod transaction_sort;
 pub mod transaction_split;
 pub mod transaction_status;
 pub mod transaction_struct;
@@ -12,14 +16,18 @@ pub mod transaction_tags;
 pub mod transaction_transfer;
 pub mod transaction_type;
 
+pub use cashflow_query::QueryCashflow;
 pub use transaction_complexity::TransactionComplexity;
-pub(crate) use transaction_date::julian_date_from_u32;
+pub(crate) use transaction_date::{julian_date_from_u32, julian_date_to_u32};
 pub use transaction_error::TransactionError;
+pub use transaction_flags::TransactionFlags;
+pub use transaction_group::{group_transactions, GroupBy, TransactionGroup};
 pub use transaction_query::QueryTransactions;
 pub use transaction_simple::SimpleTransaction;
-pub use transaction_split::{parse_split_values, SplitTransaction};
+pub use transaction_sort::{sort_transactions, SortKey, TransactionSort};
+pub use transaction_split::{join_split_values, parse_split_values, SplitTransaction};
 pub use transaction_status::TransactionStatus;
-pub use transaction_struct::{sum_transactions, Transaction};
-pub(crate) use transaction_tags::split_tags;
+pub use transaction_struct::{sum_transactions, sum_transactions_in, Transaction};
+pub(crate) use transaction_tags::{join_tags, split_tags};
 pub use transaction_transfer::Transfer;
 pub use transaction_type::TransactionType;
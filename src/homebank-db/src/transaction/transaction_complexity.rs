@@ -1,6 +1,7 @@
 //! A wrapper to provide a shared interface for [`SimpleTransaction`s][crate::transaction::transaction_simple::SimpleTransaction] and [`SplitTransaction`s][crate::transaction::transaction_split::SplitTransaction].
 
 use super::{SimpleTransaction, SplitTransaction};
+use crate::Money;
 
 /// A wrapper to provide a shared interface for [`SimpleTransaction`s][crate::transaction::transaction_simple::SimpleTransaction] and [`SplitTransaction`s][crate::transaction::transaction_split::SplitTransaction].
 #[derive(Debug, PartialEq, Clone)]
@@ -34,7 +35,7 @@ impl TransactionComplexity {
 
     /// Retrieve the total for the [`Transaction`][crate::transaction::transaction_struct::Transaction].
     /// This is simply the amount of a [`SimpleTransaction`][crate::transaction::transaction_simple::SimpleTransaction] or the sum of all amounts in a [`SplitTransaction`][crate::transaction::transaction_split::SplitTransaction].
-    pub fn total(&self) -> f32 {
+    pub fn total(&self) -> Money {
         match self {
             Self::Simple(simple) => *simple.amount(),
             Self::Split(split) => split.total(),
@@ -50,7 +51,7 @@ impl TransactionComplexity {
     }
 
     /// Retrieve the amount(s) for the [`Transaction`][crate::transaction::transaction_struct::Transaction].
-    pub fn amounts(&self) -> Vec<&f32> {
+    pub fn amounts(&self) -> Vec<&Money> {
         match self {
             Self::Simple(simple_tr) => vec![simple_tr.amount()],
             Self::Split(split_tr) => split_tr.amounts(),
@@ -36,6 +36,12 @@ pub(crate) fn unclamped_julian_date_from_u32(d: u32) -> NaiveDate {
     *JULIAN_ZERO + Duration::days(d.into())
 }
 
+/// Convert a [`NaiveDate`] into the Julian format (encoded as days since [`struct@JULIAN_ZERO`]) used in the HomeBank XML file.
+/// This is the inverse of [`unclamped_julian_date_from_u32`].
+pub(crate) fn julian_date_to_u32(d: &NaiveDate) -> u32 {
+    (*d - *JULIAN_ZERO).num_days() as u32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +176,27 @@ mod tests {
 
         check_unclamped_date(input, expected);
     }
+
+    #[track_caller]
+    fn check_date_to_julian(input: NaiveDate, expected: u32) {
+        let observed = julian_date_to_u32(&input);
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn convert_min_date_to_julian() {
+        let input = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        let expected = 693596;
+
+        check_date_to_julian(input, expected);
+    }
+
+    #[test]
+    fn convert_unix_epoch_beginning_to_julian() {
+        let input = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let expected = 719163;
+
+        check_date_to_julian(input, expected);
+    }
 }
@@ -56,4 +56,12 @@ pub enum TransactionError {
     /// When the category, memo, or other fields in a transaction are incompatible with either a [`SimpleTransaction`][crate::transaction::transaction_simple::SimpleTransaction] or a [`SplitTransaction`][crate::transaction::transaction_split::SplitTransaction].
     #[error("Transactions must be `SimpleTransaction` or `SplitTransaction`, but not both. `SplitTransaction`s cannot have a global category and `SimpleTransaction`s cannot have multiple memos or amounts.")]
     ConflictingInfoSimpleSplitTransaction,
+
+    /// When the field to sort transactions by is invalid.
+    #[error("Invalid sort order `{0}`. Must be 'date', 'amount', 'payee', 'category', 'account', 'memo', or 'status', optionally with a leading '-' for descending order.")]
+    InvalidSortKey(String),
+
+    /// When the field to group transactions by is invalid.
+    #[error("Invalid group `{0}`. Must be 'week', 'month', 'year', 'payee', 'category', 'account', 'tag', 'paymode', or 'status'.")]
+    InvalidGroupBy(String),
 }
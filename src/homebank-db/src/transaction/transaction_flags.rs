@@ -0,0 +1,54 @@
+//! Flags set on a [`Transaction`][crate::transaction::transaction_struct::Transaction].
+
+use crate::flags::flag_set;
+
+flag_set! {
+    /// Flags set on a [`Transaction`][crate::transaction::transaction_struct::Transaction].
+    pub struct TransactionFlags {
+        /// The transaction is income rather than an expense (`OF_INCOME`).
+        const INCOME = 1 << 1;
+
+        /// The transaction is scheduled to be posted automatically (`OF_AUTO`).
+        /// This is only set on [`Template`s][crate::template::template_struct::Template].
+        const AUTO = 1 << 2;
+
+        /// The transaction is a reminder (`OF_REMIND`).
+        /// Newer versions of HomeBank use [`TransactionStatus::Remind`][crate::transaction::transaction_status::TransactionStatus::Remind] instead.
+        const REMIND = 1 << 5;
+
+        /// The cheque number comes from the second cheque book (`OF_CHEQ2`).
+        const CHEQ2 = 1 << 6;
+
+        /// The schedule only occurs a limited number of times (`OF_LIMIT`).
+        /// This is only set on [`Template`s][crate::template::template_struct::Template].
+        const LIMIT = 1 << 7;
+
+        /// The transaction is split across several categories (`OF_SPLIT`).
+        const SPLIT = 1 << 8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_bits_are_kept() {
+        let flags = TransactionFlags::from(0b1_0000_0011);
+
+        assert!(flags.contains(TransactionFlags::SPLIT));
+        assert!(flags.contains(TransactionFlags::INCOME));
+        assert!(!flags.contains(TransactionFlags::AUTO));
+        assert_eq!("259", flags.to_string());
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut flags = TransactionFlags::from(132);
+        flags.remove(TransactionFlags::AUTO | TransactionFlags::LIMIT);
+        assert_eq!(TransactionFlags::empty(), flags);
+
+        flags.insert(TransactionFlags::INCOME);
+        assert_eq!(2, flags.bits());
+    }
+}
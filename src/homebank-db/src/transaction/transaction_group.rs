@@ -0,0 +1,329 @@
+//! Group [`Transaction`s][crate::transaction::transaction_struct::Transaction] together and summarize each group.
+
+use super::{sum_transactions_in, Transaction, TransactionError};
+use crate::{HomeBankDb, Interval, Money};
+use std::{collections::BTreeMap, str::FromStr};
+
+/// What to group [`Transaction`s][crate::transaction::transaction_struct::Transaction] by.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GroupBy {
+    /// The week the transaction is in, starting on Monday.
+    Week,
+
+    /// The month the transaction is in.
+    Month,
+
+    /// The year the transaction is in.
+    Year,
+
+    /// The transaction's [`Payee`][crate::payee::payee_struct::Payee].
+    Payee,
+
+    /// The [`Category`][crate::category::category_struct::Category] of each line of the transaction.
+    Category,
+
+    /// The transaction's [`Account`][crate::account::account_struct::Account].
+    Account,
+
+    /// Each of the transaction's tags.
+    Tag,
+
+    /// The transaction's [`PayMode`][crate::paymode::paymode_struct::PayMode].
+    PayMode,
+
+    /// The transaction's [`TransactionStatus`][crate::transaction::transaction_status::TransactionStatus].
+    Status,
+}
+
+impl GroupBy {
+    /// Find the name of each group that a transaction belongs to, with the part of the transaction in that group.
+    ///
+    /// Each line of a split transaction goes into the group for its category, and a transaction with several tags goes into the group for each tag.
+    /// A transaction without a payee, category, or tag goes into a group with no name.
+    fn keys(&self, tr: &Transaction, db: &HomeBankDb) -> Vec<(Option<String>, Transaction)> {
+        let period = |interval: Interval| Some(interval.label(&interval.start_of(tr.date())));
+
+        match self {
+            GroupBy::Week => vec![(period(Interval::Week), tr.clone())],
+            GroupBy::Month => vec![(period(Interval::Month), tr.clone())],
+            GroupBy::Year => vec![(period(Interval::Year), tr.clone())],
+            GroupBy::Payee => vec![(tr.payee_name(db), tr.clone())],
+            GroupBy::Category if tr.is_split() => tr
+                .category_names(db)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, name)| tr.subset(&[i]).map(|line| (name, line)))
+                .collect(),
+            GroupBy::Category => {
+                let name = tr.category_names(db).into_iter().flatten().next();
+                vec![(name, tr.clone())]
+            }
+            GroupBy::Account => vec![(tr.account_name(db), tr.clone())],
+            GroupBy::Tag => match tr.tags() {
+                Some(tags) if !tags.is_empty() => tags
+                    .iter()
+                    .map(|tag| (Some(tag.clone()), tr.clone()))
+                    .collect(),
+                _ => vec![(None, tr.clone())],
+            },
+            GroupBy::PayMode => vec![(Some(tr.pay_mode().to_string()), tr.clone())],
+            GroupBy::Status => vec![(Some(tr.status().to_string()), tr.clone())],
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "week" => Ok(GroupBy::Week),
+            "month" => Ok(GroupBy::Month),
+            "year" => Ok(GroupBy::Year),
+            "payee" => Ok(GroupBy::Payee),
+            "category" => Ok(GroupBy::Category),
+            "account" => Ok(GroupBy::Account),
+            "tag" => Ok(GroupBy::Tag),
+            "paymode" => Ok(GroupBy::PayMode),
+            "status" => Ok(GroupBy::Status),
+            _ => Err(TransactionError::InvalidGroupBy(s.to_string())),
+        }
+    }
+}
+
+/// The number of [`Transaction`s][crate::transaction::transaction_struct::Transaction] in a group, and their sum, smallest, and largest amounts.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransactionGroup {
+    /// Name of the group, or `None` for transactions without a payee, category, or tag.
+    name: Option<String>,
+
+    /// Number of transactions, or lines of split transactions, in the group.
+    count: usize,
+
+    /// Sum of the amounts.
+    sum: Money,
+
+    /// Smallest amount.
+    min: Money,
+
+    /// Largest amount.
+    max: Money,
+
+    /// Whether any of the amounts were converted from another currency.
+    converted: bool,
+}
+
+impl TransactionGroup {
+    /// Create a new summary of a group
+    pub fn new(
+        name: Option<&str>,
+        count: usize,
+        sum: Money,
+        min: Money,
+        max: Money,
+        converted: bool,
+    ) -> Self {
+        Self {
+            name: name.map(|s| s.to_string()),
+            count,
+            sum,
+            min,
+            max,
+            converted,
+        }
+    }
+
+    /// Retrieve the name of the group, if it has one
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Retrieve the number of transactions in the group
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Retrieve the sum of the amounts
+    pub fn sum(&self) -> Money {
+        self.sum
+    }
+
+    /// Retrieve the average amount
+    pub fn average(&self) -> Money {
+        self.sum
+            .checked_div((self.count as u64).into())
+            .unwrap_or(Money::ZERO)
+    }
+
+    /// Retrieve the smallest amount
+    pub fn min(&self) -> Money {
+        self.min
+    }
+
+    /// Retrieve the largest amount
+    pub fn max(&self) -> Money {
+        self.max
+    }
+
+    /// Check if any of the amounts were converted from another currency
+    pub fn is_converted(&self) -> bool {
+        self.converted
+    }
+}
+
+/// Group transactions together, and summarize each group in a single [`Currency`][crate::currency::currency_struct::Currency].
+///
+/// Groups are sorted by name, with the group with no name last.
+pub fn group_transactions(
+    v: &[Transaction],
+    by: GroupBy,
+    db: &HomeBankDb,
+    currency: usize,
+) -> Vec<TransactionGroup> {
+    let mut groups: BTreeMap<(bool, Option<String>), Vec<Transaction>> = BTreeMap::new();
+    for tr in v {
+        for (name, part) in by.keys(tr, db) {
+            groups.entry((name.is_none(), name)).or_default().push(part);
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, name), transactions)| {
+            let mut converted = false;
+            let amounts: Vec<Money> = transactions
+                .iter()
+                .map(|tr| {
+                    let (amount, tr_converted) =
+                        sum_transactions_in(std::slice::from_ref(tr), db, currency);
+                    converted |= tr_converted;
+                    amount
+                })
+                .collect();
+
+            TransactionGroup::new(
+                name.as_deref(),
+                amounts.len(),
+                amounts.iter().sum(),
+                amounts.iter().copied().min().unwrap_or_default(),
+                amounts.iter().copied().max().unwrap_or_default(),
+                converted,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check_group(by: GroupBy, expected: Vec<TransactionGroup>) {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<pay key="1" name="Grocer"/>
+<account key="1" name="Chequing"/>
+<cat key="1" name="Food"/>
+<cat key="2" name="Fun"/>
+<ope date="737791" amount="-10" account="1" payee="1" category="1" tags="weekly"/>
+<ope date="737792" amount="-30" account="1" payee="1" flags="256" scat="1||2" samt="-25||-5" smem="||"/>
+<ope date="737826" amount="-20" account="1" category="2" tags="weekly treat"/>
+<ope date="737827" amount="-1" account="1"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+        let observed = group_transactions(db.transactions(), by, &db, 0);
+
+        assert_eq!(expected, observed);
+    }
+
+    #[test]
+    fn group_by_month() {
+        let expected = vec![
+            TransactionGroup::new(
+                Some("2021-01"),
+                2,
+                Money::from(-40),
+                Money::from(-30),
+                Money::from(-10),
+                false,
+            ),
+            TransactionGroup::new(
+                Some("2021-02"),
+                2,
+                Money::from(-21),
+                Money::from(-20),
+                Money::from(-1),
+                false,
+            ),
+        ];
+
+        check_group(GroupBy::Month, expected.clone());
+        assert_eq!(Money::from(-20), expected[0].average());
+        assert_eq!(Ok(GroupBy::PayMode), GroupBy::from_str("paymode"));
+    }
+
+    #[test]
+    fn group_split_lines_by_category() {
+        let expected = vec![
+            TransactionGroup::new(
+                Some("Food"),
+                2,
+                Money::from(-35),
+                Money::from(-25),
+                Money::from(-10),
+                false,
+            ),
+            TransactionGroup::new(
+                Some("Fun"),
+                2,
+                Money::from(-25),
+                Money::from(-20),
+                Money::from(-5),
+                false,
+            ),
+            TransactionGroup::new(
+                None,
+                1,
+                Money::from(-1),
+                Money::from(-1),
+                Money::from(-1),
+                false,
+            ),
+        ];
+
+        check_group(GroupBy::Category, expected);
+    }
+
+    #[test]
+    fn group_by_each_tag() {
+        let expected = vec![
+            TransactionGroup::new(
+                Some("treat"),
+                1,
+                Money::from(-20),
+                Money::from(-20),
+                Money::from(-20),
+                false,
+            ),
+            TransactionGroup::new(
+                Some("weekly"),
+                2,
+                Money::from(-30),
+                Money::from(-20),
+                Money::from(-10),
+                false,
+            ),
+            TransactionGroup::new(
+                None,
+                2,
+                Money::from(-31),
+                Money::from(-30),
+                Money::from(-1),
+                false,
+            ),
+        ];
+
+        check_group(GroupBy::Tag, expected);
+    }
+}
@@ -1,7 +1,10 @@
 //! Options for filtering [`Transaction`s][crate::transaction::transaction_struct::Transaction] from the [`HomeBankDb`].
 
-use super::{TransactionStatus, TransactionType};
-use crate::{HomeBankDb, PayMode, Query, Transaction};
+use super::{sort_transactions, TransactionSort, TransactionStatus, TransactionType};
+use crate::{
+    interval::{parse_date, parse_date_to, IntervalError},
+    DateExpr, HomeBankDb, Money, PayMode, Query, Transaction,
+};
 use chrono::NaiveDate;
 use clap::Parser;
 use regex::Regex;
@@ -16,30 +19,44 @@ use std::str::FromStr;
 )]
 pub struct QueryTransactions {
     /// Include transactions starting from (and including) this date.
+    /// Dates can also be relative, like 'today' or '-30d', or the start of a period, like '2026-Q2' or 'last-month'.
     #[clap(
         short = 'd',
         long = "date-from",
-        parse(try_from_str = NaiveDate::from_str),
+        parse(try_from_str = parse_date),
+        allow_hyphen_values = true,
         value_name = "date"
     )]
     date_from: Option<NaiveDate>,
 
     /// Include transactions up to (and excluding) this date.
+    /// A period, like '2026-Q2' or 'last-month', is included up to its end.
     #[clap(
         short = 'D',
         long = "date-to",
-        parse(try_from_str = NaiveDate::from_str),
+        parse(try_from_str = parse_date_to),
+        allow_hyphen_values = true,
         value_name = "date"
     )]
     date_to: Option<NaiveDate>,
 
+    /// Include transactions in this period, instead of `--date-from` and `--date-to`.
+    /// Use a period like '2026-Q2', '2026-03', 'last-month', or 'ytd', or a relative date like '-30d' for the days since then.
+    #[clap(
+        long = "period",
+        conflicts_with_all = &["date-from", "date-to"],
+        allow_hyphen_values = true,
+        value_name = "period"
+    )]
+    period: Option<PeriodDates>,
+
     /// Include transactions greater than (and including) this amount.
     #[clap(
         short = 'l',
         long = "amount-lower",
         value_name = "amount"
     )]
-    amount_from: Option<f32>,
+    amount_from: Option<Money>,
 
     /// Include transactions less than (and excluding) this amount.
     #[clap(
@@ -47,7 +64,7 @@ pub struct QueryTransactions {
         long = "amount-upper",
         value_name = "amount"
     )]
-    amount_to: Option<f32>,
+    amount_to: Option<Money>,
 
     /// Include transactions with a certain status.
     #[clap(
@@ -120,15 +137,34 @@ pub struct QueryTransactions {
         value_name = "type"
     )]
     transaction_type: Option<Vec<TransactionType>>,
+
+    /// Sort transactions by these fields, separated by commas.
+    /// Use 'date', 'amount', 'payee', 'category', 'account', 'memo', or 'status', with a leading '-' for descending order.
+    #[clap(
+        long = "sort",
+        value_name = "field",
+        use_value_delimiter = true,
+        allow_hyphen_values = true
+    )]
+    sort: Option<Vec<TransactionSort>>,
+
+    /// Skip this many transactions, after sorting.
+    #[clap(long = "offset", value_name = "N")]
+    offset: Option<usize>,
+
+    /// Include at most this many transactions, after sorting and skipping.
+    #[clap(long = "limit", value_name = "N")]
+    limit: Option<usize>,
 }
 
 impl QueryTransactions {
     /// Create a new query for `Transaction`s
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         date_from: &Option<NaiveDate>,
         date_to: &Option<NaiveDate>,
-        amount_from: &Option<f32>,
-        amount_to: &Option<f32>,
+        amount_from: &Option<Money>,
+        amount_to: &Option<Money>,
         status: &Option<Vec<TransactionStatus>>,
         category: &Option<Regex>,
         payee: &Option<Regex>,
@@ -142,6 +178,7 @@ impl QueryTransactions {
         Self {
             date_from: *date_from,
             date_to: *date_to,
+            period: None,
             amount_from: *amount_from,
             amount_to: *amount_to,
             status: status.clone(),
@@ -153,26 +190,59 @@ impl QueryTransactions {
             info: info.clone(),
             tags: tags.clone(),
             transaction_type: transaction_type.clone(),
+            sort: None,
+            offset: None,
+            limit: None,
         }
     }
 
+    /// Sort the transactions by these fields, in turn
+    pub fn with_sort(mut self, sort: &[TransactionSort]) -> Self {
+        self.sort = Some(sort.to_vec());
+        self
+    }
+
+    /// Skip this many transactions, after sorting
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Include at most this many transactions, after sorting and skipping
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Only include transactions in a period, instead of between two dates
+    pub fn with_period(mut self, period: DateExpr) -> Self {
+        self.period = Some(PeriodDates::from(period));
+        self
+    }
+
     /// Select the lower bound date for querying
     pub fn date_from(&self) -> &Option<NaiveDate> {
-        &self.date_from
+        match &self.period {
+            Some(period) => &period.date_from,
+            None => &self.date_from,
+        }
     }
 
     /// Select the upper bound date for querying
     pub fn date_to(&self) -> &Option<NaiveDate> {
-        &self.date_to
+        match &self.period {
+            Some(period) => &period.date_to,
+            None => &self.date_to,
+        }
     }
 
     /// Select the lower bound amount for querying
-    pub fn amount_from(&self) -> &Option<f32> {
+    pub fn amount_from(&self) -> &Option<Money> {
         &self.amount_from
     }
 
     /// Select the upper bound amount for querying
-    pub fn amount_to(&self) -> &Option<f32> {
+    pub fn amount_to(&self) -> &Option<Money> {
         &self.amount_to
     }
 
@@ -221,6 +291,21 @@ impl QueryTransactions {
         &self.transaction_type
     }
 
+    /// Select the fields to sort by
+    pub fn sort(&self) -> &Option<Vec<TransactionSort>> {
+        &self.sort
+    }
+
+    /// Select the number of transactions to skip
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Select the maximum number of transactions to include
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
     /// Filter out dates occurring before the query date
     pub fn filter_date_from(&self, tr: &Transaction) -> bool {
         match self.date_from() {
@@ -364,11 +449,35 @@ impl QueryTransactions {
     }
 }
 
+/// The dates covered by a `--period`, standing in for `--date-from` and `--date-to`.
+#[derive(Debug, Clone, Copy)]
+struct PeriodDates {
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+}
+
+impl From<DateExpr> for PeriodDates {
+    fn from(period: DateExpr) -> Self {
+        Self {
+            date_from: Some(period.start()),
+            date_to: Some(period.end()),
+        }
+    }
+}
+
+impl FromStr for PeriodDates {
+    type Err = IntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateExpr::from_str(s).map(Self::from)
+    }
+}
+
 impl Query for QueryTransactions {
     type T = Transaction;
 
     fn exec(&self, db: &HomeBankDb) -> Vec<Self::T> {
-        let filt_transactions: Vec<Transaction> = db
+        let mut filt_transactions: Vec<Transaction> = db
             .transactions()
             .iter()
             .filter(|&tr| self.filter_date_from(tr))
@@ -386,6 +495,14 @@ impl Query for QueryTransactions {
             .filter_map(|tr| self.filter_category(tr, db))
             .collect();
 
+        if let Some(orders) = self.sort() {
+            sort_transactions(&mut filt_transactions, orders, db);
+        }
+
         filt_transactions
+            .into_iter()
+            .skip(self.offset().unwrap_or(0))
+            .take(self.limit().unwrap_or(usize::MAX))
+            .collect()
     }
 }
@@ -1,5 +1,7 @@
 //! A simple [`Transaction`][crate::transaction::transaction_struct::Transaction] that only belongs to a single [`Category`][crate::category::category_struct::Category].
 
+use crate::Money;
+
 /// A simple [`Transaction`][crate::transaction::transaction_struct::Transaction] that only belongs to a single [`Category`][crate::category::category_struct::Category].
 #[derive(Debug, PartialEq, Clone)]
 pub struct SimpleTransaction {
@@ -9,7 +11,7 @@ pub struct SimpleTransaction {
     /// The amount of the parent [`Transaction`][crate::transaction::transaction_struct::Transaction].
     /// This will duplicate data, but this impacts the code base much less
     /// than using pointers and introducing lifetimes everywhere.
-    amount: f32,
+    amount: Money,
 
     /// The memo of the parent [`Transaction`][crate::transaction::transaction_struct::Transaction].
     /// This will duplicate data, but this impacts the code base much less
@@ -22,13 +24,13 @@ impl SimpleTransaction {
     pub fn empty() -> Self {
         Self {
             category: None,
-            amount: 0.0,
+            amount: Money::ZERO,
             memo: None,
         }
     }
 
     /// Create an new [`SimpleTransaction`]
-    pub fn new(category: Option<usize>, amount: f32, memo: Option<String>) -> Self {
+    pub fn new(category: Option<usize>, amount: Money, memo: Option<String>) -> Self {
         Self {
             category,
             amount,
@@ -47,12 +49,12 @@ impl SimpleTransaction {
     }
 
     /// Retrieve the amount of the [`Transaction`][crate::transaction::transaction_struct::Transaction].
-    pub fn amount(&self) -> &f32 {
+    pub fn amount(&self) -> &Money {
         &self.amount
     }
 
     /// Retrieve the mutable amount of the [`Transaction`][crate::transaction::transaction_struct::Transaction].
-    pub fn mut_amount(&mut self) -> &mut f32 {
+    pub fn mut_amount(&mut self) -> &mut Money {
         &mut self.amount
     }
 
@@ -0,0 +1,179 @@
+//! Orders for sorting [`Transaction`s][crate::transaction::transaction_struct::Transaction].
+
+use super::{Transaction, TransactionError};
+use crate::HomeBankDb;
+use std::{cmp::Ordering, str::FromStr};
+
+/// Field of a [`Transaction`][crate::transaction::transaction_struct::Transaction] to sort by.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortKey {
+    Date,
+    Amount,
+    Payee,
+    Category,
+    Account,
+    Memo,
+    Status,
+}
+
+/// A field to sort [`Transaction`s][crate::transaction::transaction_struct::Transaction] by, and in which direction.
+///
+/// Parsed from the name of the field, like `date`, with a leading `-` to sort in descending order, like `-amount`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TransactionSort {
+    /// Field to sort by.
+    key: SortKey,
+
+    /// Whether to sort from largest to smallest.
+    descending: bool,
+}
+
+impl TransactionSort {
+    /// Create a new sort order
+    pub fn new(key: SortKey, descending: bool) -> Self {
+        Self { key, descending }
+    }
+
+    /// Retrieve the field to sort by
+    pub fn key(&self) -> SortKey {
+        self.key
+    }
+
+    /// Check if the order is from largest to smallest
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Compare two transactions by this field.
+    ///
+    /// Names are compared case-insensitively, and transactions without a payee, category, or memo come first.
+    pub fn compare(&self, a: &Transaction, b: &Transaction, db: &HomeBankDb) -> Ordering {
+        let lowercase = |name: Option<String>| name.map(|s| s.to_lowercase());
+        let ordering = match self.key {
+            SortKey::Date => a.date().cmp(b.date()),
+            SortKey::Amount => a.total().cmp(b.total()),
+            SortKey::Payee => lowercase(a.payee_name(db)).cmp(&lowercase(b.payee_name(db))),
+            SortKey::Category => {
+                let first_category = |tr: &Transaction| {
+                    tr.category_names(db)
+                        .into_iter()
+                        .flatten()
+                        .next()
+                        .map(|s| s.to_lowercase())
+                };
+                first_category(a).cmp(&first_category(b))
+            }
+            SortKey::Account => lowercase(a.account_name(db)).cmp(&lowercase(b.account_name(db))),
+            SortKey::Memo => lowercase(a.memo().clone()).cmp(&lowercase(b.memo().clone())),
+            SortKey::Status => usize::from(*a.status()).cmp(&usize::from(*b.status())),
+        };
+
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+impl FromStr for TransactionSort {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, descending) = match s.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (s, false),
+        };
+        let key = match name {
+            "date" => SortKey::Date,
+            "amount" => SortKey::Amount,
+            "payee" => SortKey::Payee,
+            "category" => SortKey::Category,
+            "account" => SortKey::Account,
+            "memo" => SortKey::Memo,
+            "status" => SortKey::Status,
+            _ => return Err(TransactionError::InvalidSortKey(s.to_string())),
+        };
+
+        Ok(Self::new(key, descending))
+    }
+}
+
+/// Sort transactions by each order in turn, keeping transactions that are equal in the order they were in.
+pub fn sort_transactions(v: &mut [Transaction], orders: &[TransactionSort], db: &HomeBankDb) {
+    v.sort_by(|a, b| {
+        orders
+            .iter()
+            .map(|order| order.compare(a, b, db))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Query, QueryTransactions};
+
+    #[test]
+    fn parse_sort() {
+        assert_eq!(
+            Ok(TransactionSort::new(SortKey::Amount, true)),
+            TransactionSort::from_str("-amount")
+        );
+        assert_eq!(
+            Ok(TransactionSort::new(SortKey::Payee, false)),
+            TransactionSort::from_str("payee")
+        );
+        assert_eq!(
+            Err(TransactionError::InvalidSortKey("-size".to_string())),
+            TransactionSort::from_str("-size")
+        );
+    }
+
+    const INPUT: &str = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<pay key="1" name="bakery"/>
+<pay key="2" name="Grocer"/>
+<account key="1" name="Chequing"/>
+<ope date="737792" amount="-20" account="1" payee="2"/>
+<ope date="737791" amount="-10" account="1" payee="1"/>
+<ope date="737793" amount="-30" account="1" payee="2"/>
+<ope date="737794" amount="-5" account="1"/>
+</homebank>
+"#;
+
+    fn amounts(transactions: &[Transaction]) -> Vec<i64> {
+        transactions
+            .iter()
+            .map(|tr| tr.total().to_f64() as i64)
+            .collect()
+    }
+
+    #[test]
+    fn sort_by_several_fields() {
+        let db = HomeBankDb::from_xml(INPUT.as_bytes());
+        let mut observed = db.transactions().clone();
+        let orders = [
+            TransactionSort::from_str("payee").unwrap(),
+            TransactionSort::from_str("-date").unwrap(),
+        ];
+        sort_transactions(&mut observed, &orders, &db);
+
+        assert_eq!(vec![-5, -10, -30, -20], amounts(&observed));
+    }
+
+    #[test]
+    fn query_with_offset_and_limit() {
+        let db = HomeBankDb::from_xml(INPUT.as_bytes());
+        let query = QueryTransactions::new(
+            &None, &None, &None, &None, &None, &None, &None, &None, &None, &None, &None, &None,
+            &None,
+        )
+        .with_sort(&[TransactionSort::new(SortKey::Amount, false)])
+        .with_offset(1)
+        .with_limit(2);
+
+        assert_eq!(vec![-20, -10], amounts(&query.exec(&db)));
+    }
+}
@@ -1,6 +1,6 @@
 //! A [`Transaction`][crate::transaction::transaction_struct::Transaction] that is split across multiple [`Categories`][crate::category::category_struct::Category].
 
-use crate::TransactionError;
+use crate::{Money, TransactionError};
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
 
@@ -18,7 +18,7 @@ pub struct SplitTransaction {
     categories: Vec<Option<usize>>,
 
     /// The amounts for each sub-transaction.
-    amounts: Vec<f32>,
+    amounts: Vec<Money>,
 
     /// The memos for each sub-transactions.
     memos: Vec<Option<String>>,
@@ -40,7 +40,7 @@ impl SplitTransaction {
     pub fn new(
         num_splits: usize,
         categories: &[Option<usize>],
-        amounts: &[f32],
+        amounts: &[Money],
         memos: &[Option<String>],
     ) -> Self {
         Self {
@@ -74,19 +74,19 @@ impl SplitTransaction {
     }
 
     /// Retrieve the total sum of the amounts.
-    pub fn total(&self) -> f32 {
-        self.amounts().iter().fold(0.0, |a, &b| a + b)
+    pub fn total(&self) -> Money {
+        self.amounts.iter().sum()
     }
 
     /// Retrieve the amounts for the splits.
-    pub fn amounts(&self) -> Vec<&f32> {
+    pub fn amounts(&self) -> Vec<&Money> {
         // using an iteration->collection trick to create the `Vec` on the fly
         // without duplicating the data inside that `Vec`
         self.amounts.iter().collect()
     }
 
     /// Retrieve the mutable amounts for the splits.
-    pub fn mut_amounts(&mut self) -> &mut Vec<f32> {
+    pub fn mut_amounts(&mut self) -> &mut Vec<Money> {
         &mut self.amounts
     }
 
@@ -117,7 +117,7 @@ impl SplitTransaction {
             .iter()
             .map(|&i| *self.categories()[i])
             .collect();
-        let sub_amounts: Vec<f32> = idx
+        let sub_amounts: Vec<Money> = idx
             .iter()
             .map(|&i| *self.amounts()[i])
             .collect();
@@ -149,6 +149,12 @@ pub fn parse_split_values(att: OwnedAttribute) -> Vec<String> {
     vals
 }
 
+/// Combine the values of a split transaction or template into a single attribute value.
+/// This is the inverse of [`parse_split_values`].
+pub fn join_split_values(v: &[String]) -> String {
+    v.join(SPLIT_SEPARATOR)
+}
+
 /// Convert `Vec<String>` into a parsed `Vec<Option<usize>>` to be used as categories.
 pub fn parse_split_cat_vec(v: &[String]) -> Result<Vec<Option<usize>>, TransactionError> {
     v.iter()
@@ -161,12 +167,12 @@ pub fn parse_split_cat_vec(v: &[String]) -> Result<Vec<Option<usize>>, Transacti
         .collect()
 }
 
-/// Convert `Vec<String>` into a parsed `Vec<f32>` to be used as amounts.
-pub fn parse_split_amount_vec(v: &[String]) -> Result<Vec<f32>, TransactionError> {
+/// Convert `Vec<String>` into a parsed `Vec<Money>` to be used as amounts.
+pub fn parse_split_amount_vec(v: &[String]) -> Result<Vec<Money>, TransactionError> {
     v.iter()
         // returning a `Result<>` within the iterator can be collected into a `Result<Vec<...>>`
         // see https://stackoverflow.com/a/26370894/7416009 for an example and other discussion
-        .map(|s| match f32::from_str(s) {
+        .map(|s| match Money::from_str(s) {
             Ok(u) => Ok(u),
             Err(_) => Err(TransactionError::InvalidCategory(s.to_string())),
         })
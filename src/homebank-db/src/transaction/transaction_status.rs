@@ -1,11 +1,12 @@
 //! Status of a [`Transaction`][crate::transaction::transaction_struct::Transaction].
 
 use super::TransactionError;
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 /// Status of a [`Transaction`][crate::transaction::transaction_struct::Transaction].
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub enum TransactionStatus {
+    #[default]
     None,
     Cleared,
     Reconciled,
@@ -13,12 +14,6 @@ pub enum TransactionStatus {
     Void,
 }
 
-impl Default for TransactionStatus {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl TryFrom<usize> for TransactionStatus {
     type Error = TransactionError;
 
@@ -34,6 +29,31 @@ impl TryFrom<usize> for TransactionStatus {
     }
 }
 
+impl From<TransactionStatus> for usize {
+    fn from(st: TransactionStatus) -> Self {
+        match st {
+            TransactionStatus::None => 0,
+            TransactionStatus::Cleared => 1,
+            TransactionStatus::Reconciled => 2,
+            TransactionStatus::Remind => 3,
+            TransactionStatus::Void => 4,
+        }
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransactionStatus::None => "none",
+            TransactionStatus::Cleared => "cleared",
+            TransactionStatus::Reconciled => "reconciled",
+            TransactionStatus::Remind => "remind",
+            TransactionStatus::Void => "void",
+        };
+        write!(f, "{name}")
+    }
+}
+
 impl FromStr for TransactionStatus {
     type Err = TransactionError;
 
@@ -1,12 +1,13 @@
 //! Individual transactions applied to one or more [`Account`s][crate::account::account_struct::Account].
 
 use super::{
-    julian_date_from_u32, parse_split_values, split_tags,
+    join_split_values, join_tags, julian_date_from_u32, julian_date_to_u32, parse_split_values,
+    split_tags,
     transaction_split::{parse_split_amount_vec, parse_split_cat_vec, parse_split_memo_vec},
-    SimpleTransaction, SplitTransaction, TransactionComplexity, TransactionStatus, TransactionType,
-    Transfer,
+    SimpleTransaction, SplitTransaction, TransactionComplexity, TransactionFlags, TransactionStatus,
+    TransactionType, Transfer,
 };
-use crate::{HomeBankDb, PayMode, TransactionError};
+use crate::{db::db_writer::xml_attribute, HomeBankDb, Money, PayMode, TransactionError};
 use chrono::NaiveDate;
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
@@ -18,7 +19,7 @@ pub struct Transaction {
     date: NaiveDate,
 
     /// Net sum of the transaction (including any split amounts).
-    amount: f32,
+    amount: Money,
     
     /// Which [`Account`][crate::account::account_struct::Account] the transaction applied to.
     account: usize,
@@ -30,7 +31,7 @@ pub struct Transaction {
     status: TransactionStatus,
     
     /// Any flags on the transaction.
-    flags: Option<usize>,
+    flags: Option<TransactionFlags>,
     
     /// Which payee was involved with the transaction.
     payee: Option<usize>,
@@ -58,7 +59,7 @@ impl Transaction {
     pub fn empty() -> Self {
         Self {
             date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
-            amount: 0.0,
+            amount: Money::ZERO,
             account: 0,
             pay_mode: PayMode::default(),
             status: TransactionStatus::default(),
@@ -73,13 +74,14 @@ impl Transaction {
     }
 
     /// Create a new [`Transaction`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         date: &NaiveDate,
-        amount: f32,
+        amount: Money,
         account: usize,
         pay_mode: &PayMode,
         status: &TransactionStatus,
-        flags: &Option<usize>,
+        flags: &Option<TransactionFlags>,
         payee: &Option<usize>,
         memo: &Option<String>,
         info: &Option<String>,
@@ -108,8 +110,13 @@ impl Transaction {
         &self.date
     }
 
+    /// Retrieve the mutable date of the [`Transaction`].
+    pub fn mut_date(&mut self) -> &mut NaiveDate {
+        &mut self.date
+    }
+
     /// Retrieve the total amount for a [`Transaction`].
-    pub fn total(&self) -> &f32 {
+    pub fn total(&self) -> &Money {
         &self.amount
     }
 
@@ -123,6 +130,25 @@ impl Transaction {
         db.accounts().get(&self.account()).map(|acct| acct.name().to_string())
     }
 
+    /// Retrieve the key of the [`Currency`][crate::currency::currency_struct::Currency] of the [`Transaction`]'s [`Account`][crate::account::account_struct::Account].
+    pub fn currency(&self, db: &HomeBankDb) -> Option<usize> {
+        db.accounts().get(&self.account()).map(|acct| acct.currency())
+    }
+
+    /// Check if the [`Transaction`]'s [`Account`][crate::account::account_struct::Account] is left out of reports.
+    pub fn is_excluded_from_reports(&self, db: &HomeBankDb) -> bool {
+        db.accounts()
+            .get(&self.account())
+            .map_or(false, |acct| acct.is_excluded_from_reports())
+    }
+
+    /// Check if the [`Transaction`]'s [`Account`][crate::account::account_struct::Account] is left out of budgets.
+    pub fn is_excluded_from_budget(&self, db: &HomeBankDb) -> bool {
+        db.accounts()
+            .get(&self.account())
+            .map_or(false, |acct| acct.is_excluded_from_budget())
+    }
+
     /// Retrieve the status of the [`Transaction`].
     pub fn status(&self) -> &TransactionStatus {
         &self.status
@@ -133,6 +159,11 @@ impl Transaction {
         &self.payee
     }
 
+    /// Retrieve the mutable [`Payee`][crate::payee::payee_struct::Payee] for the [`Transaction`].
+    pub fn mut_payee(&mut self) -> &mut Option<usize> {
+        &mut self.payee
+    }
+
     /// Retrieve the [`Payee`'s][crate::payee::payee_struct::Payee] name.
     pub fn payee_name(&self, db: &HomeBankDb) -> Option<String> {
         match self.payee() {
@@ -148,6 +179,11 @@ impl Transaction {
         &self.pay_mode
     }
 
+    /// Retrieve the mutable payment method of the [`Transaction`].
+    pub fn mut_pay_mode(&mut self) -> &mut PayMode {
+        &mut self.pay_mode
+    }
+
     /// Retrieve the memo for the [`Transaction`].
     pub fn memo(&self) -> &Option<String> {
         &self.memo
@@ -164,15 +200,41 @@ impl Transaction {
     }
 
     /// Retrieve the flags for the [`Transaction`].
-    pub fn flags(&self) -> &Option<usize> {
+    pub fn flags(&self) -> &Option<TransactionFlags> {
         &self.flags
     }
 
+    /// Retrieve the mutable flags for the [`Transaction`].
+    pub fn mut_flags(&mut self) -> &mut Option<TransactionFlags> {
+        &mut self.flags
+    }
+
+    /// Check if the [`Transaction`] changes the balance of its account.
+    /// Void transactions and reminders don't, like in HomeBank.
+    pub fn counts_towards_balance(&self) -> bool {
+        self.status != TransactionStatus::Void && !self.is_remind()
+    }
+
+    /// Check if the [`Transaction`] has a flag set.
+    pub fn has_flag(&self, flag: TransactionFlags) -> bool {
+        self.flags.map_or(false, |f| f.contains(flag))
+    }
+
+    /// Check if the [`Transaction`] is a reminder, either by its status or by the older reminder flag.
+    pub fn is_remind(&self) -> bool {
+        self.status == TransactionStatus::Remind || self.has_flag(TransactionFlags::REMIND)
+    }
+
     /// Retrieve the type for the [`Transaction`].
     pub fn ttype(&self) -> &TransactionType {
         &self.transaction_type
     }
 
+    /// Retrieve the mutable type for the [`Transaction`].
+    pub fn mut_ttype(&mut self) -> &mut TransactionType {
+        &mut self.transaction_type
+    }
+
     /// Check if the [`Transaction`] is a [`Transfer`][crate::transaction::transaction_transfer::Transfer] or not.
     pub fn is_transfer(&self) -> bool {
         self.ttype().is_transfer()
@@ -196,11 +258,56 @@ impl Transaction {
         }
     }
 
+    /// Create the other side of a [`Transfer`][crate::transaction::transaction_transfer::Transfer], as it appears in the destination [`Account`][crate::account::account_struct::Account].
+    ///
+    /// Returns `None` if the [`Transaction`] is not a transfer.
+    pub fn transfer_counterpart(&self) -> Option<Self> {
+        let (key, dst) = match self.ttype() {
+            TransactionType::Transfer(xfer) => (*xfer.transfer_key(), *xfer.destination()),
+            _ => return None,
+        };
+
+        let complexity = match &self.complexity {
+            TransactionComplexity::Simple(simple) => TransactionComplexity::Simple(
+                SimpleTransaction::new(*simple.category(), -*simple.amount(), simple.memo().clone()),
+            ),
+            TransactionComplexity::Split(split) => {
+                let mut split = split.clone();
+                split.mut_amounts().iter_mut().for_each(|amt| *amt = -*amt);
+                TransactionComplexity::Split(split)
+            }
+        };
+        // money leaving one account is income for the other
+        let flags = self.flags.map(|mut f| {
+            if self.amount.is_negative() {
+                f.insert(TransactionFlags::INCOME);
+            } else {
+                f.remove(TransactionFlags::INCOME);
+            }
+            f
+        });
+
+        Some(Self {
+            date: self.date,
+            amount: -self.amount,
+            account: dst,
+            flags,
+            transaction_type: TransactionType::Transfer(Transfer::new(key, self.account)),
+            complexity,
+            ..self.clone()
+        })
+    }
+
     /// Check if the [`Transaction`] is a [`SplitTransaction`][crate::transaction::transaction_split::SplitTransaction] or not.
     pub fn is_split(&self) -> bool {
         self.complexity.is_split()
     }
 
+    /// Retrieve the mutable [`TransactionComplexity`], which holds the [`Categories`][crate::category::category_struct::Category] of the [`Transaction`].
+    pub fn mut_complexity(&mut self) -> &mut TransactionComplexity {
+        &mut self.complexity
+    }
+
     /// Retrieve the number of splits the [`Transaction`] is divided into.
     pub fn num_splits(&self) -> usize {
         self.complexity.num_splits()
@@ -225,7 +332,7 @@ impl Transaction {
     }
 
     /// Retrieve the amounts for a [`Transaction`].
-    pub fn amounts(&self) -> Vec<&f32> {
+    pub fn amounts(&self) -> Vec<&Money> {
         self.complexity.amounts()
     }
 
@@ -253,6 +360,19 @@ impl Transaction {
                 complexity,
             ))
     }
+
+    /// Subset the [`Transaction`] to the parts assigned to any of the given [`Categories`][crate::category::category_struct::Category], by their keys.
+    pub fn subset_categories(&self, keys: &[usize]) -> Option<Self> {
+        let matching_idx: Vec<usize> = self
+            .categories()
+            .iter()
+            .enumerate()
+            .filter(|(_, cat_idx)| matches!(cat_idx, Some(idx) if keys.contains(idx)))
+            .map(|(i, _)| i)
+            .collect();
+
+        self.subset(&matching_idx)
+    }
 }
 
 impl Default for Transaction {
@@ -282,7 +402,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
                     }
                 }
                 "amount" => {
-                    match f32::from_str(&i.value) {
+                    match Money::from_str(&i.value) {
                         Ok(a) => {
                             // store the total amount
                             tr.amount = a;
@@ -293,7 +413,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
                             // if the transaction already appears to be a transfer, then leave the type alone
                             // if it's not a transfer then it's an expense if the amount is negative, otherwise an income
                             if !tr.is_transfer() {
-                                if a > 0.0 {
+                                if a.is_positive() {
                                     tr.transaction_type = TransactionType::Income;
                                 } else {
                                     tr.transaction_type = TransactionType::Expense;
@@ -326,25 +446,19 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
                 },
                 "paymode" => {
                     tr.pay_mode = match usize::from_str(&i.value) {
-                        Ok(pm) => match PayMode::try_from(pm) {
-                            Ok(t_pm) => t_pm,
-                            Err(e) => return Err(e),
-                        },
+                        Ok(pm) => PayMode::try_from(pm)?,
                         Err(_) => return Err(TransactionError::InvalidPayMode),
                     }
                 }
                 "st" => {
                     tr.status = match usize::from_str(&i.value) {
-                        Ok(st) => match TransactionStatus::try_from(st) {
-                            Ok(t_stat) => t_stat,
-                            Err(e) => return Err(e),
-                        },
+                        Ok(st) => TransactionStatus::try_from(st)?,
                         Err(_) => return Err(TransactionError::InvalidStatus),
                     }
                 }
                 "flags" => {
                     tr.flags = match usize::from_str(&i.value) {
-                        Ok(f) => Some(f),
+                        Ok(f) => Some(TransactionFlags::from(f)),
                         Err(_) => return Err(TransactionError::InvalidFlags),
                     }
                 }
@@ -380,6 +494,12 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
                         }
                     }
                 }
+                "info" => {
+                    tr.info = match i.value.as_str() {
+                        "" => None,
+                        s => Some(s.to_string()),
+                    }
+                }
                 "tags" => {
                     // split the tags string by commas
                     let tags = split_tags(&i.value);
@@ -401,10 +521,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
 
                     // convert the category string into split categories
                     let raw_category_indices = parse_split_values(i);
-                    let cat_indices = match parse_split_cat_vec(&raw_category_indices) {
-                        Ok(v) => v,
-                        Err(e) => return Err(e),
-                    };
+                    let cat_indices = parse_split_cat_vec(&raw_category_indices)?;
 
                     // if the split hasn't been processed yet by another field, check that they're the same length
                     if !tr.is_split() {
@@ -434,10 +551,7 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
 
                     is_simple = Some(false);
                     let raw_amounts = parse_split_values(i);
-                    let amounts = match parse_split_amount_vec(&raw_amounts) {
-                        Ok(v) => v,
-                        Err(e) => return Err(e),
-                    };
+                    let amounts = parse_split_amount_vec(&raw_amounts)?;
 
                     // if the split hasn't been processed yet by another field, check that they're the same length
                     if !tr.is_split() {
@@ -541,9 +655,99 @@ impl TryFrom<Vec<OwnedAttribute>> for Transaction {
     }
 }
 
+impl From<&Transaction> for Vec<OwnedAttribute> {
+    fn from(tr: &Transaction) -> Self {
+        let mut v = vec![
+            xml_attribute("date", julian_date_to_u32(&tr.date)),
+            xml_attribute("amount", tr.amount),
+        ];
+
+        // HomeBank leaves out an account, pay mode, or status of 0
+        if tr.account != 0 {
+            v.push(xml_attribute("account", tr.account));
+        }
+        if let Some(dst) = tr.transfer_destination() {
+            v.push(xml_attribute("dst_account", dst));
+        }
+        if tr.pay_mode != PayMode::None {
+            v.push(xml_attribute("paymode", usize::from(tr.pay_mode)));
+        }
+        if tr.status != TransactionStatus::None {
+            v.push(xml_attribute("st", usize::from(tr.status)));
+        }
+        if let Some(flags) = tr.flags {
+            v.push(xml_attribute("flags", flags));
+        }
+        if let Some(payee) = tr.payee {
+            v.push(xml_attribute("payee", payee));
+        }
+        if let TransactionComplexity::Simple(simple) = &tr.complexity {
+            if let Some(cat) = simple.category() {
+                v.push(xml_attribute("category", cat));
+            }
+        }
+        if let Some(memo) = &tr.memo {
+            v.push(xml_attribute("wording", memo));
+        }
+        if let Some(info) = &tr.info {
+            v.push(xml_attribute("info", info));
+        }
+        if let Some(tags) = &tr.tags {
+            v.push(xml_attribute("tags", join_tags(tags)));
+        }
+        if let Some(key) = tr.transfer_key() {
+            v.push(xml_attribute("kxfer", key));
+        }
+        if let TransactionComplexity::Split(split) = &tr.complexity {
+            // HomeBank uses a category of `0` for a split without a category
+            let cats: Vec<String> = split
+                .categories()
+                .iter()
+                .map(|&cat| cat.unwrap_or(0).to_string())
+                .collect();
+            let amounts: Vec<String> = split.amounts().iter().map(|amt| amt.to_string()).collect();
+            let memos: Vec<String> = split
+                .memos()
+                .iter()
+                .map(|&memo| memo.clone().unwrap_or_default())
+                .collect();
+
+            v.push(xml_attribute("scat", join_split_values(&cats)));
+            v.push(xml_attribute("samt", join_split_values(&amounts)));
+            v.push(xml_attribute("smem", join_split_values(&memos)));
+        }
+
+        v
+    }
+}
+
 /// Sum the total amount from all the [`Transaction`]s.
-pub fn sum_transactions(v: &[Transaction]) -> f32 {
-    v.iter().fold(0.0, |sum, tr| sum + tr.total())
+pub fn sum_transactions(v: &[Transaction]) -> Money {
+    v.iter().map(|tr| tr.total()).sum()
+}
+
+/// Sum the total amount from all the [`Transaction`]s, converted into a single [`Currency`][crate::currency::currency_struct::Currency].
+///
+/// Also returns whether any of the amounts were converted from another currency.
+/// Amounts that cannot be converted, such as from a currency without a conversion rate, are left out.
+/// Use [`HomeBankDb::check_convertible`] to catch these beforehand.
+pub fn sum_transactions_in(v: &[Transaction], db: &HomeBankDb, currency: usize) -> (Money, bool) {
+    let mut converted = false;
+    let sum = v
+        .iter()
+        .map(|tr| match tr.currency(db) {
+            Some(from) => match db.convert_for_total(*tr.total(), from, currency) {
+                Some((amount, was_converted)) => {
+                    converted |= was_converted;
+                    amount
+                }
+                None => Money::ZERO,
+            },
+            None => *tr.total(),
+        })
+        .sum();
+
+    (sum, converted)
 }
 
 #[cfg(test)]
@@ -628,7 +832,7 @@ mod tests {
         let input = template_vec_ownedatt();
         let expected = Ok(Transaction {
             account: 1,
-            amount: 1.0,
+            amount: Money::from(1),
             date: NaiveDate::from_ymd_opt(2020, 3, 11).unwrap(),
             flags: None,
             info: None,
@@ -766,7 +970,7 @@ mod tests {
     fn parse_positive_amount() {
         let input = r#"<ope amount="1">"#;
         let expected = Ok(Transaction {
-            amount: 1.0,
+            amount: Money::from(1),
             transaction_type: TransactionType::Income,
             ..Default::default()
         });
@@ -778,7 +982,7 @@ mod tests {
     fn parse_negative_amount() {
         let input = r#"<ope amount="-1">"#;
         let expected = Ok(Transaction {
-            amount: -1.0,
+            amount: Money::from(-1),
             transaction_type: TransactionType::Expense,
             ..Default::default()
         });
@@ -790,7 +994,7 @@ mod tests {
     fn parse_good_category() {
         let input = r#"<ope category="1">"#;
         let expected = Ok(Transaction {
-            complexity: TransactionComplexity::Simple(SimpleTransaction::new(Some(1), 0.0, None)),
+            complexity: TransactionComplexity::Simple(SimpleTransaction::new(Some(1), Money::from(0), None)),
             ..Default::default()
         });
 
@@ -902,13 +1106,29 @@ mod tests {
     fn parse_good_flag() {
         let input = r#"<ope flags="1">"#;
         let expected = Ok(Transaction {
-            flags: Some(1),
+            flags: Some(TransactionFlags::from(1)),
             ..Default::default()
         });
 
         check_try_from_single_str(input, expected);
     }
 
+    #[test]
+    fn remind_by_status_or_flag() {
+        let by_status = Transaction {
+            status: TransactionStatus::Remind,
+            ..Default::default()
+        };
+        let by_flag = Transaction {
+            flags: Some(TransactionFlags::REMIND),
+            ..Default::default()
+        };
+
+        assert!(by_status.is_remind());
+        assert!(by_flag.is_remind());
+        assert!(!Transaction::default().is_remind());
+    }
+
     #[test]
     fn parse_bad_flags() {
         let input = r#"<ope flags="somethingelse">"#;
@@ -1020,16 +1240,16 @@ mod tests {
         let input = r#"<ope date="736696" amount="-1088.72" account="5" paymode="8" st="2" flags="256" payee="13" scat="83||100" samt="-1119.8||31.079999999999998" smem="January||Internet payment (Dec 1 - Dec 30)"/>"#;
         let expected = Ok(Transaction {
             date: NaiveDate::from_ymd_opt(2018, 1, 2).unwrap(),
-            amount: -1088.72,
+            amount: Money::new(-108872, 2),
             account: 5,
             pay_mode: PayMode::Deposit,
             status: TransactionStatus::Reconciled,
-            flags: Some(256),
+            flags: Some(TransactionFlags::SPLIT),
             payee: Some(13),
             complexity: TransactionComplexity::Split(SplitTransaction::new(
                 2,
                 &[Some(83), Some(100)],
-                &[-1119.80, 31.08],
+                &[Money::new(-11198, 1), Money::new(31079999999999998, 15)],
                 &[
                     Some(String::from("January")),
                     Some(String::from("Internet payment (Dec 1 - Dec 30)")),
@@ -1047,16 +1267,16 @@ mod tests {
         let input = r#"<ope date="736696" amount="-1088.72" account="5" paymode="8" st="2" flags="256" payee="13" samt="-1119.8||31.079999999999998" scat="83||100" smem="January||Internet payment (Dec 1 - Dec 30)"/>"#;
         let expected = Ok(Transaction {
             date: NaiveDate::from_ymd_opt(2018, 1, 2).unwrap(),
-            amount: -1088.72,
+            amount: Money::new(-108872, 2),
             account: 5,
             pay_mode: PayMode::Deposit,
             status: TransactionStatus::Reconciled,
-            flags: Some(256),
+            flags: Some(TransactionFlags::SPLIT),
             payee: Some(13),
             complexity: TransactionComplexity::Split(SplitTransaction::new(
                 2,
                 &[Some(83), Some(100)],
-                &[-1119.80, 31.08],
+                &[Money::new(-11198, 1), Money::new(31079999999999998, 15)],
                 &[
                     Some(String::from("January")),
                     Some(String::from("Internet payment (Dec 1 - Dec 30)")),
@@ -1083,7 +1303,7 @@ mod tests {
         let input = r#"<ope date="736696" amount="-300" account="1" paymode="4" st="2" payee="1" kxfer="10" dst_account="2"/>"#;
         let expected = Ok(Transaction {
             date: NaiveDate::from_ymd_opt(2018, 1, 2).unwrap(),
-            amount: -300.0,
+            amount: Money::from(-300),
             account: 1,
             pay_mode: PayMode::BankTransfer,
             status: TransactionStatus::Reconciled,
@@ -1123,6 +1343,159 @@ mod tests {
         check_try_from_single_str(input, expected);
     }
 
+    #[test]
+    fn parse_info() {
+        let input = r#"<ope info="Ref #123">"#;
+        let expected = Ok(Transaction {
+            info: Some(String::from("Ref #123")),
+            ..Default::default()
+        });
+
+        check_try_from_single_str(input, expected);
+    }
+
+    #[track_caller]
+    fn check_round_trip(input: &str) {
+        let mut reader = EventReader::from_str(input);
+        let (_start, first) = (reader.next(), reader.next());
+
+        if let Ok(XmlEvent::StartElement { attributes, .. }) = first {
+            let original = Transaction::try_from(attributes).unwrap();
+            let written: Vec<OwnedAttribute> = (&original).into();
+            let observed = Transaction::try_from(written);
+
+            assert_eq!(Ok(original), observed);
+        } else {
+            panic!("Incorrect string passed into check. `{:#?}`", first);
+        }
+    }
+
+    #[test]
+    fn round_trip_simple() {
+        let input = r#"<ope date="737495" amount="-12.5" account="1" paymode="5" st="1" flags="2" payee="3" category="4" wording="Lunch &amp; coffee" info="1234" tags="food work"/>"#;
+
+        check_round_trip(input);
+    }
+
+    #[test]
+    fn round_trip_split() {
+        let input = r#"<ope date="736696" amount="-1088.72" account="5" paymode="8" st="2" flags="256" payee="13" scat="83||100" samt="-1119.8||31.079999999999998" smem="January||"/>"#;
+
+        check_round_trip(input);
+    }
+
+    #[test]
+    fn round_trip_transfer() {
+        let input = r#"<ope date="736696" amount="-300" account="1" dst_account="2" paymode="4" st="2" payee="1" kxfer="10"/>"#;
+
+        check_round_trip(input);
+    }
+
+    #[track_caller]
+    fn parse_first_element(input: &str) -> Transaction {
+        let mut reader = EventReader::from_str(input);
+        let (_start, first) = (reader.next(), reader.next());
+
+        if let Ok(XmlEvent::StartElement { attributes, .. }) = first {
+            Transaction::try_from(attributes).unwrap()
+        } else {
+            panic!("Incorrect string passed into check. `{:#?}`", first);
+        }
+    }
+
+    #[test]
+    fn transfer_counterpart() {
+        let tr = parse_first_element(
+            r#"<ope date="736696" amount="-300" account="1" dst_account="2" flags="0" category="3" kxfer="10"/>"#,
+        );
+        let expected = parse_first_element(
+            r#"<ope date="736696" amount="300" account="2" dst_account="1" flags="2" category="3" kxfer="10"/>"#,
+        );
+        let observed = tr.transfer_counterpart();
+
+        assert_eq!(Some(expected), observed);
+        assert_eq!(Some(tr), observed.unwrap().transfer_counterpart());
+    }
+
+    #[test]
+    fn no_counterpart_without_transfer() {
+        let tr = parse_first_element(r#"<ope date="736696" amount="-300" account="1"/>"#);
+
+        assert_eq!(None, tr.transfer_counterpart());
+    }
+
+    #[test]
+    fn sum_transactions_to_the_cent() {
+        // amounts as HomeBank writes them, which a float would drift on after a few thousand entries
+        let v: Vec<Transaction> = (0..5000)
+            .flat_map(|_| {
+                [
+                    parse_first_element(
+                        r#"<ope date="736696" amount="339.01999999999998" account="1"/>"#,
+                    ),
+                    parse_first_element(
+                        r#"<ope date="736696" amount="-0.10000000000000001" account="1"/>"#,
+                    ),
+                ]
+            })
+            .collect();
+        let observed = sum_transactions(&v);
+
+        assert_eq!("1694600.00", format!("{observed:.2}"));
+    }
+
+    #[test]
+    fn sum_transactions_in_currency() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<properties title="" curr="1"/>
+<cur key="1" flags="0" iso="CAD" name="Canadian Dollar" symb="$" syprf="1" dchar="." gchar="," frac="2" rate="0" mdate="0"/>
+<cur key="2" flags="0" iso="EUR" name="Euro" symb="€" syprf="0" dchar="," gchar=" " frac="2" rate="0.68000000000000005" mdate="0"/>
+<account key="1" curr="1" name="Chequing"/>
+<account key="2" curr="2" name="Euro account"/>
+<ope date="736696" amount="-100" account="1"/>
+<ope date="736696" amount="-68" account="2"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+
+        assert_eq!(Money::from(-168), sum_transactions(db.transactions()));
+        assert_eq!(
+            (Money::from(-200), true),
+            sum_transactions_in(db.transactions(), &db, 1)
+        );
+        assert_eq!(
+            (Money::from(-136), true),
+            sum_transactions_in(db.transactions(), &db, 2)
+        );
+        assert_eq!(
+            (Money::from(-100), false),
+            sum_transactions_in(&db.transactions()[..1], &db, 1)
+        );
+    }
+
+    #[test]
+    fn sum_transactions_in_leaves_out_unconvertible() {
+        let input = r#"<?xml version="1.0"?>
+<homebank v="1.4" d="050504">
+<properties title="" curr="1"/>
+<cur key="1" flags="0" iso="CAD" name="Canadian Dollar" symb="$" syprf="1" dchar="." gchar="," frac="2" rate="0" mdate="0"/>
+<cur key="2" flags="0" iso="XXX" name="No rate" symb="?" syprf="1" dchar="." gchar="," frac="2" rate="0" mdate="0"/>
+<account key="1" curr="1" name="Chequing"/>
+<account key="2" curr="2" name="No rate account"/>
+<ope date="736696" amount="-100" account="1"/>
+<ope date="736696" amount="-68" account="2"/>
+</homebank>
+"#;
+        let db = HomeBankDb::from_xml(input.as_bytes());
+
+        assert_eq!(
+            (Money::from(-100), false),
+            sum_transactions_in(db.transactions(), &db, 1)
+        );
+        assert!(db.check_convertible(1).is_err());
+    }
+
     #[track_caller]
     fn check_subset(input: (Transaction, Vec<usize>), expected: Option<Transaction>) {
         let tr = input.0;
@@ -1163,16 +1536,16 @@ mod tests {
     fn subset_split() {
         let tr = Transaction {
             date: NaiveDate::from_ymd_opt(2018, 1, 2).unwrap(),
-            amount: -1088.72,
+            amount: Money::new(-108872, 2),
             account: 5,
             pay_mode: PayMode::Deposit,
             status: TransactionStatus::Reconciled,
-            flags: Some(256),
+            flags: Some(TransactionFlags::SPLIT),
             payee: Some(13),
             complexity: TransactionComplexity::Split(SplitTransaction::new(
                 2,
                 &[Some(83), Some(100)],
-                &[-1119.80, 31.08],
+                &[Money::new(-11198, 1), Money::new(3108, 2)],
                 &[
                     Some(String::from("January")),
                     Some(String::from("Internet payment (Dec 1 - Dec 30)")),
@@ -1183,16 +1556,16 @@ mod tests {
         let idx = vec![0];
         let expected = Some(Transaction {
             date: NaiveDate::from_ymd_opt(2018, 1, 2).unwrap(),
-            amount: -1119.80,
+            amount: Money::new(-11198, 1),
             account: 5,
             pay_mode: PayMode::Deposit,
             status: TransactionStatus::Reconciled,
-            flags: Some(256),
+            flags: Some(TransactionFlags::SPLIT),
             payee: Some(13),
             complexity: TransactionComplexity::Split(SplitTransaction::new(
                 1,
                 &[Some(83)],
-                &[-1119.80],
+                &[Money::new(-11198, 1)],
                 &[Some(String::from("January"))],
             )),
             ..Default::default()
@@ -1205,16 +1578,16 @@ mod tests {
     fn subset_split_empty_index() {
         let tr = Transaction {
             date: NaiveDate::from_ymd_opt(2018, 1, 2).unwrap(),
-            amount: -1088.72,
+            amount: Money::new(-108872, 2),
             account: 5,
             pay_mode: PayMode::Deposit,
             status: TransactionStatus::Reconciled,
-            flags: Some(256),
+            flags: Some(TransactionFlags::SPLIT),
             payee: Some(13),
             complexity: TransactionComplexity::Split(SplitTransaction::new(
                 2,
                 &[Some(83), Some(100)],
-                &[-1119.80, 31.08],
+                &[Money::new(-11198, 1), Money::new(3108, 2)],
                 &[
                     Some(String::from("January")),
                     Some(String::from("Internet payment (Dec 1 - Dec 30)"))
@@ -11,3 +11,8 @@ pub(crate) fn split_tags(s: &str) -> Vec<String> {
         .filter(|s| !s.is_empty())
         .collect()
 }
+
+/// Combine the list of tags for a [`Transaction`][crate::transaction::transaction_struct::Transaction] into a single string for the HomeBank XML file.
+pub(crate) fn join_tags(tags: &[String]) -> String {
+    tags.join(&TAG_SEPARATOR.to_string())
+}
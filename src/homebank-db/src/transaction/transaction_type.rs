@@ -2,13 +2,14 @@
 
 use super::Transfer;
 use crate::TransactionError;
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 /// The type of a [`Transaction`][crate::transaction::transaction_struct::Transaction].
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub enum TransactionType {
     /// An amount that is withdrawn from an [`Account`][crate::account::account_struct::Account].
     /// Also known as a "credit" in a [double-entry bookkeeping system](https://en.wikipedia.org/wiki/Double-entry_bookkeeping).
+    #[default]
     Expense,
 
     /// An amount that is given to an [`Account`][crate::account::account_struct::Account].
@@ -33,9 +34,14 @@ impl TransactionType {
     }
 }
 
-impl Default for TransactionType {
-    fn default() -> Self {
-        Self::Expense
+impl fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransactionType::Expense => "expense",
+            TransactionType::Income => "income",
+            TransactionType::Transfer(_) => "transfer",
+        };
+        write!(f, "{name}")
     }
 }
 